@@ -0,0 +1,291 @@
+//! Command-line interface for checking and querying wordlists
+//!
+//! Exit codes:
+//! - `0`: Success.
+//! - `1`: A check failed, or a lookup or prefix search found nothing.
+//! - `2`: Invalid usage, or the wordlist could not be read or parsed.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use wl_tools::{
+    ParseWordlistError, ParsedWordlist, WordCharDawg, WordCharTreeRootNode, WordRadixTreeRootNode,
+};
+
+const USAGE: &str = "\
+Usage: wl-tools <COMMAND> <WORDLIST> [ARGS...]
+
+The wordlist is a file with one word per line, or `-` to read from stdin.
+Plain, Diceware, BIP-39 and CSV lists are detected automatically.
+Blank lines and lines starting with `#` are ignored.
+
+Commands:
+  check <WORDLIST> [--iterative]    Check for duplicates and well-formedness.
+                                    With --iterative, also require that the list
+                                    is suitable for iterative char search.
+  stats <WORDLIST>                  Print statistics about the word char tree.
+  render <WORDLIST>                 Draw the word char tree.
+  lookup <WORDLIST> <WORD>...       Print the index of each word.
+  prefix <WORDLIST> <PREFIX>        Print the words that start with the prefix.
+  gen <WORDLIST> [-n <COUNT>] [-s <SEPARATOR>]
+                                    Generate a passphrase of COUNT random words
+                                    (default 6), joined by SEPARATOR (default ' ').
+
+Exit codes:
+  0  Success
+  1  A check failed, or a lookup or prefix search found nothing
+  2  Invalid usage, or the wordlist could not be read or parsed
+";
+
+const EXIT_FAILURE: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match run(&args) {
+        Ok(code) => code,
+        Err(msg) => {
+            eprintln!("wl-tools: {msg}");
+            ExitCode::from(EXIT_USAGE)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    let (command, path, rest) = match args {
+        [command, path, rest @ ..] => (command.as_str(), path.as_str(), rest),
+        [command] if command == "-h" || command == "--help" => {
+            print!("{USAGE}");
+            return Ok(ExitCode::SUCCESS);
+        }
+        _ => return Err(format!("missing arguments\n\n{USAGE}")),
+    };
+    let wordlist = load_path(path)?;
+    match command {
+        "check" => check(&wordlist, rest),
+        "stats" => no_args(rest).map(|()| stats(&wordlist.tree)),
+        "render" => no_args(rest).map(|()| {
+            print!("{}", wordlist.tree.render());
+            ExitCode::SUCCESS
+        }),
+        "lookup" => lookup(&wordlist.tree, rest),
+        "prefix" => match rest {
+            [prefix] => Ok(prefix_search(&wordlist.tree, prefix)),
+            _ => Err("prefix takes exactly one prefix".to_string()),
+        },
+        "gen" => gen(&wordlist.tree, rest),
+        _ => Err(format!("unknown command `{command}`\n\n{USAGE}")),
+    }
+}
+
+fn no_args(rest: &[String]) -> Result<(), String> {
+    match rest {
+        [] => Ok(()),
+        [arg, ..] => Err(format!("unexpected argument `{arg}`")),
+    }
+}
+
+struct Wordlist {
+    tree: WordCharTreeRootNode<'static, String>,
+    /// Line numbers of words that duplicate an earlier word, and of the earlier word
+    duplicates: Vec<(usize, usize)>,
+}
+
+fn load_path(path: &str) -> Result<Wordlist, String> {
+    let mut input = String::new();
+    let read = if path == "-" {
+        io::stdin().read_to_string(&mut input)
+    } else {
+        File::open(path).and_then(|mut file| file.read_to_string(&mut input))
+    };
+    read.map_err(|err| format!("failed to read `{path}`: {err}"))?;
+    load(&input).map_err(|err| format!("failed to parse `{path}`: {err}"))
+}
+
+fn load(input: &str) -> Result<Wordlist, ParseWordlistError> {
+    let parsed = ParsedWordlist::parse(input)?;
+    let mut first_lines = HashMap::new();
+    let mut duplicates = vec![];
+    for record in &parsed.records {
+        if let Some(&first_line) = first_lines.get(&record.word.to_lowercase()) {
+            duplicates.push((record.line, first_line));
+        } else {
+            first_lines.insert(record.word.to_lowercase(), record.line);
+        }
+    }
+    Ok(Wordlist {
+        tree: parsed.to_tree(),
+        duplicates,
+    })
+}
+
+fn check(wordlist: &Wordlist, rest: &[String]) -> Result<ExitCode, String> {
+    let require_iterative = match rest {
+        [] => false,
+        [flag] if flag == "--iterative" => true,
+        [arg, ..] => return Err(format!("unexpected argument `{arg}`")),
+    };
+    let tree = &wordlist.tree;
+    let mut ok = true;
+
+    for &(line, first_line) in &wordlist.duplicates {
+        println!("line {line}: duplicate of the word on line {first_line}");
+        ok = false;
+    }
+    if !tree.is_fully_well_formed() {
+        println!("the word char tree is not fully well-formed");
+        ok = false;
+    }
+    if !tree.is_suitable_for_iterative_char_search() {
+        for word in tree.words() {
+            let num_longer = tree.words_with_prefix(word).count() - 1;
+            if num_longer > 0 {
+                println!("`{word}` is a prefix of {num_longer} other word(s)");
+            }
+        }
+        println!("the wordlist is not suitable for iterative char search");
+        ok &= !require_iterative;
+    }
+
+    if ok {
+        println!("ok: {} words", tree.len());
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::from(EXIT_FAILURE))
+    }
+}
+
+fn stats(tree: &WordCharTreeRootNode<String>) -> ExitCode {
+    let num_words = tree.len();
+    let total_chars = tree.words().map(|w| w.chars().count()).sum::<usize>();
+    let dawg = WordCharDawg::from(tree);
+    let report = dawg.compression_report();
+    println!("words: {num_words}");
+    if num_words > 0 {
+        println!("bits of entropy per word: {:.2}", (num_words as f64).log2());
+        println!(
+            "average word length: {:.2}",
+            total_chars as f64 / num_words as f64
+        );
+    }
+    println!("max depth: {}", tree.get_max_depth());
+    println!("nodes: {}", tree.node_count());
+    println!(
+        "radix tree nodes: {}",
+        WordRadixTreeRootNode::from(tree).node_count()
+    );
+    println!(
+        "dawg nodes: {} ({:.1}% of tree)",
+        report.dawg_node_count,
+        report.ratio() * 100.0
+    );
+    println!("fully well-formed: {}", tree.is_fully_well_formed());
+    println!(
+        "suitable for iterative char search: {}",
+        tree.is_suitable_for_iterative_char_search()
+    );
+    ExitCode::SUCCESS
+}
+
+fn lookup(tree: &WordCharTreeRootNode<String>, words: &[String]) -> Result<ExitCode, String> {
+    if words.is_empty() {
+        return Err("lookup takes at least one word".to_string());
+    }
+    let mut found_all = true;
+    for word in words {
+        match tree.get_index(word) {
+            Some(idx) => println!("{idx}\t{}", tree.get_by_index(idx).unwrap()),
+            None => {
+                eprintln!("{word}: not found");
+                found_all = false;
+            }
+        }
+    }
+    Ok(if found_all {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_FAILURE)
+    })
+}
+
+fn prefix_search(tree: &WordCharTreeRootNode<String>, prefix: &str) -> ExitCode {
+    let Some(idx_range) = tree.prefix_range(prefix) else {
+        eprintln!("{prefix}: no words with this prefix");
+        return ExitCode::from(EXIT_FAILURE);
+    };
+    for (idx, word) in idx_range.zip(tree.words_with_prefix(prefix)) {
+        println!("{idx}\t{word}");
+    }
+    ExitCode::SUCCESS
+}
+
+fn gen(tree: &WordCharTreeRootNode<String>, rest: &[String]) -> Result<ExitCode, String> {
+    let mut count = 6;
+    let mut separator = " ".to_string();
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        let value = rest
+            .next()
+            .ok_or_else(|| format!("missing value for `{arg}`"))?;
+        match arg.as_str() {
+            "-n" => {
+                count = value
+                    .parse()
+                    .map_err(|_| format!("invalid word count `{value}`"))?
+            }
+            "-s" => separator = value.clone(),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+    if tree.is_empty() {
+        return Err("cannot generate a passphrase from an empty wordlist".to_string());
+    }
+    let words = (0..count)
+        .map(|_| {
+            random_index(tree.len())
+                .map(|idx| tree.get_by_index(idx).unwrap().as_str())
+                .map_err(|err| format!("failed to get random bytes: {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    println!("{}", words.join(&separator));
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Get a uniformly random index below `n`, using the randomness of the operating system
+fn random_index(n: usize) -> Result<usize, getrandom::Error> {
+    let n = n as u64;
+    // Reject values from the incomplete last multiple of `n` to avoid modulo bias.
+    let zone = u64::MAX - u64::MAX % n;
+    loop {
+        let mut buf = [0; 8];
+        getrandom::getrandom(&mut buf)?;
+        let value = u64::from_le_bytes(buf);
+        if value < zone {
+            return Ok((value % n) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_skips_blank_lines_and_finds_duplicates() {
+        let wordlist = load("arm\n\n  army \nman\nArm\n").unwrap();
+        assert_eq!(wordlist.tree.len(), 3);
+        assert_eq!(wordlist.tree.get("army"), Some(&"army".to_string()));
+        assert_eq!(wordlist.duplicates, vec![(5, 1)]);
+    }
+
+    #[test]
+    fn test_random_index_in_range() {
+        for n in [1, 2, 7776] {
+            for _ in 0..100 {
+                assert!(random_index(n).unwrap() < n);
+            }
+        }
+    }
+}
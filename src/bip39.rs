@@ -0,0 +1,225 @@
+use std::fmt;
+
+use crate::WordCharTreeRootNode;
+
+mod sha256;
+
+use sha256::sha256;
+
+/// Number of words in a BIP-39 wordlist
+const NUM_WORDS: usize = 2048;
+
+/// Number of bits that each word of a mnemonic encodes
+const BITS_PER_WORD: usize = 11;
+
+/// Number of leading chars that uniquely identify each word in a BIP-39 wordlist
+const UNIQUE_PREFIX_LEN: usize = 4;
+
+/// Encodes entropy as BIP-39 mnemonics over the words of a word char tree, and back
+///
+/// The tree must hold exactly 2048 words. The bytes of entropy are followed by
+/// the first `8 * entropy.len() / 32` bits of their SHA-256 digest as a checksum,
+/// and each 11 bits, most significant first, give the index of a word.
+pub struct Bip39<'t, 'a, W> {
+    tree: &'t WordCharTreeRootNode<'a, W>,
+}
+
+impl<'t, 'a, W> Bip39<'t, 'a, W> {
+    /// Create a BIP-39 adapter over a tree with 2048 words
+    pub fn new(tree: &'t WordCharTreeRootNode<'a, W>) -> Result<Self, Bip39Error> {
+        if tree.len() != NUM_WORDS {
+            return Err(Bip39Error::WrongWordCount(tree.len()));
+        }
+        Ok(Self { tree })
+    }
+
+    /// Encode entropy of 16, 20, 24, 28 or 32 bytes as a mnemonic of 12 to 24 words
+    pub fn encode(&self, entropy: &[u8]) -> Result<Vec<&'t W>, Bip39Error> {
+        if !(16..=32).contains(&entropy.len()) || !entropy.len().is_multiple_of(4) {
+            return Err(Bip39Error::InvalidEntropyLength(entropy.len()));
+        }
+        let checksum_len = entropy.len() * 8 / 32;
+        let mut bits = entropy.to_vec();
+        bits.push(sha256(entropy)[0]);
+        let num_words = (entropy.len() * 8 + checksum_len) / BITS_PER_WORD;
+        Ok((0..num_words)
+            .map(|i| {
+                let idx = read_bits(&bits, i * BITS_PER_WORD, BITS_PER_WORD);
+                self.tree.get_by_index(idx).expect("index within tree")
+            })
+            .collect())
+    }
+
+    /// Decode a mnemonic of 12, 15, 18, 21 or 24 words to the entropy that it encodes
+    ///
+    /// Each word may also be given as a prefix of at least four chars that only one word starts with.
+    pub fn decode(&self, words: &[&str]) -> Result<Vec<u8>, Bip39Error> {
+        if !(12..=24).contains(&words.len()) || !words.len().is_multiple_of(3) {
+            return Err(Bip39Error::InvalidMnemonicLength(words.len()));
+        }
+        let mut bits = vec![0; (words.len() * BITS_PER_WORD).div_ceil(8)];
+        for (position, word) in words.iter().enumerate() {
+            let idx = self.index_of(word).ok_or_else(|| Bip39Error::UnknownWord {
+                position,
+                word: word.to_string(),
+            })?;
+            write_bits(&mut bits, position * BITS_PER_WORD, BITS_PER_WORD, idx);
+        }
+        let checksum_len = words.len() * BITS_PER_WORD / 33;
+        let entropy_len = checksum_len * 32 / 8;
+        let entropy = &bits[..entropy_len];
+        let checksum = read_bits(&bits, entropy_len * 8, checksum_len);
+        if checksum != (sha256(entropy)[0] >> (8 - checksum_len)) as usize {
+            return Err(Bip39Error::ChecksumMismatch);
+        }
+        Ok(entropy.to_vec())
+    }
+
+    /// Get the index of a word, or of the only word that starts with a prefix of at least four chars
+    fn index_of(&self, word: &str) -> Option<usize> {
+        if let Some(idx) = self.tree.get_index(word) {
+            return Some(idx);
+        }
+        if word.chars().count() < UNIQUE_PREFIX_LEN {
+            return None;
+        }
+        let idx_range = self.tree.prefix_range(word)?;
+        (idx_range.start() == idx_range.end()).then_some(*idx_range.start())
+    }
+}
+
+/// Read `len` bits starting at bit `start`, most significant bit first
+fn read_bits(bytes: &[u8], start: usize, len: usize) -> usize {
+    (start..start + len).fold(0, |value, bit| {
+        value << 1 | (bytes[bit / 8] >> (7 - bit % 8) & 1) as usize
+    })
+}
+
+/// Write the `len` lowest bits of `value` starting at bit `start`, most significant bit first
+fn write_bits(bytes: &mut [u8], start: usize, len: usize, value: usize) {
+    for i in 0..len {
+        let bit = start + i;
+        if value >> (len - 1 - i) & 1 == 1 {
+            bytes[bit / 8] |= 0x80 >> (bit % 8);
+        }
+    }
+}
+
+/// Error returned by [`Bip39`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bip39Error {
+    /// The tree does not hold exactly 2048 words
+    WrongWordCount(usize),
+    /// The entropy is not 16, 20, 24, 28 or 32 bytes long
+    InvalidEntropyLength(usize),
+    /// The mnemonic is not 12, 15, 18, 21 or 24 words long
+    InvalidMnemonicLength(usize),
+    /// A word of the mnemonic is neither a word of the tree nor a unique prefix of one
+    UnknownWord { position: usize, word: String },
+    /// The checksum bits of the mnemonic do not match the entropy
+    ChecksumMismatch,
+}
+
+impl fmt::Display for Bip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongWordCount(len) => {
+                write!(f, "expected {NUM_WORDS} words in the tree, found {len}")
+            }
+            Self::InvalidEntropyLength(len) => write!(f, "invalid entropy length {len}"),
+            Self::InvalidMnemonicLength(len) => write!(f, "invalid mnemonic length {len}"),
+            Self::UnknownWord { position, word } => {
+                write!(f, "unknown word {word:?} at position {position}")
+            }
+            Self::ChecksumMismatch => write!(f, "checksum mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for Bip39Error {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    /// Create a tree of 2048 words, with the first words `aaaa`, `aaab`, `aaac`, ...
+    fn example_tree() -> WordCharTreeRootNode<'static, String> {
+        (0..NUM_WORDS)
+            .map(|i| {
+                [i / (26 * 26 * 26), i / (26 * 26), i / 26, i]
+                    .map(|n| char::from(b'a' + (n % 26) as u8))
+                    .iter()
+                    .chain(['x', 'y'].iter())
+                    .collect::<String>()
+            })
+            .map(|word| (word.clone(), word))
+            .collect()
+    }
+
+    #[test]
+    fn test_encode_zero_entropy() {
+        let tree = example_tree();
+        let bip39 = Bip39::new(&tree).unwrap();
+        let mnemonic = bip39.encode(&[0; 16]).unwrap();
+        // The checksum of 16 zero bytes starts with the bits 0011,
+        // as in the mnemonic `abandon abandon ... about` of the English BIP-39 list.
+        let mut expected = vec![tree.get_by_index(0).unwrap(); 11];
+        expected.push(tree.get_by_index(3).unwrap());
+        assert_eq!(mnemonic, expected);
+    }
+
+    #[test_case(&[0x7f; 16])]
+    #[test_case(&[0x80; 20])]
+    #[test_case(&[0xff; 24])]
+    #[test_case(&(0..28).collect::<Vec<u8>>())]
+    #[test_case(&(0..32).map(|i| i * 7).collect::<Vec<u8>>())]
+    fn test_positive_round_trip(entropy: &[u8]) {
+        let tree = example_tree();
+        let bip39 = Bip39::new(&tree).unwrap();
+        let mnemonic = bip39.encode(entropy).unwrap();
+        assert_eq!(mnemonic.len(), entropy.len() * 3 / 4);
+        let words = mnemonic.iter().map(|w| w.as_str()).collect::<Vec<_>>();
+        assert_eq!(bip39.decode(&words).unwrap(), entropy);
+    }
+
+    #[test]
+    fn test_positive_decode_unique_prefixes() {
+        let tree = example_tree();
+        let bip39 = Bip39::new(&tree).unwrap();
+        let mnemonic = bip39.encode(&[0x42; 16]).unwrap();
+        let prefixes = mnemonic.iter().map(|w| &w[..4]).collect::<Vec<_>>();
+        assert_eq!(bip39.decode(&prefixes).unwrap(), [0x42; 16]);
+    }
+
+    #[test_case(&["aaaaxy"; 11], Bip39Error::InvalidMnemonicLength(11))]
+    #[test_case(&["aaaaxy"; 12], Bip39Error::ChecksumMismatch)]
+    #[test_case(&["aaa"; 12], Bip39Error::UnknownWord { position: 0, word: "aaa".to_string() })]
+    #[test_case(&["aaaaxz"; 12], Bip39Error::UnknownWord { position: 0, word: "aaaaxz".to_string() })]
+    fn test_negative_decode(words: &[&str], expected: Bip39Error) {
+        let tree = example_tree();
+        let bip39 = Bip39::new(&tree).unwrap();
+        assert_eq!(bip39.decode(words), Err(expected));
+    }
+
+    #[test_case(15)]
+    #[test_case(17)]
+    #[test_case(36)]
+    fn test_negative_encode_invalid_entropy_length(len: usize) {
+        let tree = example_tree();
+        let bip39 = Bip39::new(&tree).unwrap();
+        assert_eq!(
+            bip39.encode(&vec![0; len]),
+            Err(Bip39Error::InvalidEntropyLength(len))
+        );
+    }
+
+    #[test]
+    fn test_negative_new_wrong_word_count() {
+        let tree: WordCharTreeRootNode<usize> = (0..2047).map(|i| (format!("{i:04}"), i)).collect();
+        let Err(err) = Bip39::new(&tree) else {
+            panic!("tree of 2047 words was accepted");
+        };
+        assert_eq!(err, Bip39Error::WrongWordCount(2047));
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+
+use crate::WordCharTreeRootNode;
+
+/// Encodes bytes as sequences of words of a word char tree, and back
+///
+/// With N words in the tree, bytes are converted to words as a big number,
+/// from base 256 to base N, where the index of each word is one digit.
+/// Both bases use [bijective numeration], where the digits run from 1 to the base instead of
+/// from 0, so that every byte string has exactly one encoding and every word sequence decodes
+/// to exactly one byte string. In particular, leading zero bytes and the length of the
+/// input are preserved, and the empty byte string encodes as the empty word sequence.
+///
+/// [bijective numeration]: https://en.wikipedia.org/wiki/Bijective_numeration
+pub struct WordCodec<'t, 'a, W> {
+    tree: &'t WordCharTreeRootNode<'a, W>,
+}
+
+impl<'t, 'a, W> WordCodec<'t, 'a, W> {
+    /// Create a codec over a tree with at least two words
+    pub fn new(tree: &'t WordCharTreeRootNode<'a, W>) -> Result<Self, WordCodecError> {
+        if tree.len() < 2 {
+            return Err(WordCodecError::TooFewWords(tree.len()));
+        }
+        Ok(Self { tree })
+    }
+
+    /// Encode bytes as a sequence of words
+    pub fn encode(&self, bytes: &[u8]) -> Vec<&'t W> {
+        let value = BigUint::from_bijective_digits(bytes.iter().map(|&b| b as u64), 256);
+        value
+            .into_bijective_digits(self.tree.len() as u64)
+            .into_iter()
+            .map(|idx| {
+                self.tree
+                    .get_by_index(idx as usize)
+                    .expect("index within tree")
+            })
+            .collect()
+    }
+
+    /// Decode a sequence of words to the bytes that it encodes
+    pub fn decode(&self, words: &[&str]) -> Result<Vec<u8>, WordCodecError> {
+        let indices = words
+            .iter()
+            .enumerate()
+            .map(|(position, word)| {
+                self.tree
+                    .get_index(word)
+                    .map(|idx| idx as u64)
+                    .ok_or_else(|| WordCodecError::UnknownWord {
+                        position,
+                        word: word.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = BigUint::from_bijective_digits(indices, self.tree.len() as u64);
+        Ok(value
+            .into_bijective_digits(256)
+            .into_iter()
+            .map(|b| b as u8)
+            .collect())
+    }
+}
+
+/// Unsigned big number, as 32-bit limbs with the least significant limb first
+struct BigUint(Vec<u32>);
+
+impl BigUint {
+    /// Read zero-based digits, most significant first, as bijective digits one higher
+    fn from_bijective_digits(digits: impl IntoIterator<Item = u64>, base: u64) -> Self {
+        let mut value = Self(vec![]);
+        for digit in digits {
+            value.mul_add(base, digit + 1);
+        }
+        value
+    }
+
+    /// Write as zero-based digits, most significant first, of bijective digits one higher
+    fn into_bijective_digits(mut self, base: u64) -> Vec<u64> {
+        let mut digits = vec![];
+        while !self.0.is_empty() {
+            // Take the remainder as the digit, using `base` instead of zero.
+            let digit = match self.div_rem(base) {
+                0 => {
+                    self.sub_one();
+                    base
+                }
+                rem => rem,
+            };
+            digits.push(digit - 1);
+        }
+        digits.reverse();
+        digits
+    }
+
+    /// Set the value to `value * m + a`
+    fn mul_add(&mut self, m: u64, a: u64) {
+        let mut carry = a as u128;
+        for limb in &mut self.0 {
+            let product = *limb as u128 * m as u128 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        while carry > 0 {
+            self.0.push(carry as u32);
+            carry >>= 32;
+        }
+    }
+
+    /// Set the value to `value / d` and return `value % d`
+    fn div_rem(&mut self, d: u64) -> u64 {
+        let mut rem = 0u128;
+        for limb in self.0.iter_mut().rev() {
+            let dividend = rem << 32 | *limb as u128;
+            *limb = (dividend / d as u128) as u32;
+            rem = dividend % d as u128;
+        }
+        self.trim();
+        rem as u64
+    }
+
+    /// Set the value to `value - 1`, for a value that is not zero
+    fn sub_one(&mut self) {
+        for limb in &mut self.0 {
+            let (diff, borrow) = limb.overflowing_sub(1);
+            *limb = diff;
+            if !borrow {
+                break;
+            }
+        }
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+}
+
+/// Error returned by [`WordCodec`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordCodecError {
+    /// The tree holds fewer than two words
+    TooFewWords(usize),
+    /// A word of the sequence is not a word of the tree
+    UnknownWord { position: usize, word: String },
+}
+
+impl fmt::Display for WordCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewWords(len) => {
+                write!(f, "expected at least 2 words in the tree, found {len}")
+            }
+            Self::UnknownWord { position, word } => {
+                write!(f, "unknown word {word:?} at position {position}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordCodecError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::word_char_test::example_tree;
+    use test_case::test_case;
+
+    #[test_case(&[])]
+    #[test_case(&[0])]
+    #[test_case(&[0, 0, 0])]
+    #[test_case(&[0, 0, 1])]
+    #[test_case(&[255])]
+    #[test_case(&[1, 0])]
+    #[test_case(&[0xde, 0xad, 0xbe, 0xef])]
+    #[test_case(&[0xff; 33])]
+    fn test_positive_round_trip(bytes: &[u8]) {
+        let tree = example_tree(&["ant", "art", "i", "main", "man", "mane", "mango"]);
+        let codec = WordCodec::new(&tree).unwrap();
+        let words = codec.encode(bytes);
+        let words = words.iter().map(|w| w.as_str()).collect::<Vec<_>>();
+        assert_eq!(codec.decode(&words).unwrap(), bytes);
+    }
+
+    #[test_case(&[0], &["arm"])]
+    #[test_case(&[2], &["man"])]
+    #[test_case(&[3], &["arm", "arm"])]
+    #[test_case(&[0, 0], &["army", "man", "arm", "arm", "army"])]
+    fn test_encode_bijective_base_n(bytes: &[u8], expected: &[&str]) {
+        // The byte string [0] is the bijective base-256 number 1, [2] is 3, [3] is 4 = 1 * 3 + 1,
+        // and [0, 0] is 257 = (((2 * 3 + 3) * 3 + 1) * 3 + 1) * 3 + 2.
+        let tree = example_tree(&["arm", "army", "man"]);
+        let codec = WordCodec::new(&tree).unwrap();
+        assert_eq!(codec.encode(bytes), expected);
+    }
+
+    #[test]
+    fn test_every_word_sequence_round_trips() {
+        let tree = example_tree(&["arm", "army", "man"]);
+        let codec = WordCodec::new(&tree).unwrap();
+        for a in tree.words() {
+            for b in tree.words() {
+                let bytes = codec.decode(&[a, b]).unwrap();
+                assert_eq!(codec.encode(&bytes), [a, b]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_decode_unknown_word() {
+        let tree = example_tree(&["arm", "army", "man"]);
+        let codec = WordCodec::new(&tree).unwrap();
+        assert_eq!(
+            codec.decode(&["arm", "leg"]),
+            Err(WordCodecError::UnknownWord {
+                position: 1,
+                word: "leg".to_string()
+            })
+        );
+    }
+
+    #[test_case(&[])]
+    #[test_case(&["arm"])]
+    fn test_negative_new_too_few_words(words: &[&str]) {
+        let tree = example_tree(words);
+        assert_eq!(
+            WordCodec::new(&tree).err(),
+            Some(WordCodecError::TooFewWords(words.len()))
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use crate::WordCharTreeRootNode;
+
+/// Number of sides of each die
+const DIE_SIDES: usize = 6;
+
+/// Maps sequences of dice rolls to the words of a word char tree, and back
+///
+/// The tree must hold exactly 6<sup>k</sup> words, for some number of dice k ≥ 1.
+/// Each die shows 1 to 6, and the sequence of rolls read as a base-6 number gives the index
+/// of the word, so that `[1, 1, 1, 1, 1]` maps to the first word and `[6, 6, 6, 6, 6]` to the last.
+pub struct Diceware<'t, 'a, W> {
+    tree: &'t WordCharTreeRootNode<'a, W>,
+    num_dice: usize,
+}
+
+impl<'t, 'a, W> Diceware<'t, 'a, W> {
+    /// Create a Diceware adapter over a tree with 6<sup>k</sup> words
+    pub fn new(tree: &'t WordCharTreeRootNode<'a, W>) -> Result<Self, DicewareError> {
+        let len = tree.len();
+        let mut num_dice = 0;
+        let mut num_sequences = 1;
+        while num_sequences < len {
+            num_sequences *= DIE_SIDES;
+            num_dice += 1;
+        }
+        if num_dice == 0 || num_sequences != len {
+            return Err(DicewareError::NotAPowerOfSix { len });
+        }
+        Ok(Self { tree, num_dice })
+    }
+
+    /// Get the number of dice rolled for each word
+    pub fn num_dice(&self) -> usize {
+        self.num_dice
+    }
+
+    /// Get the index of the word for a sequence of dice rolls
+    pub fn index_of_rolls(&self, rolls: &[u8]) -> Result<usize, DicewareError> {
+        if rolls.len() != self.num_dice {
+            return Err(DicewareError::WrongNumberOfDice {
+                expected: self.num_dice,
+                found: rolls.len(),
+            });
+        }
+        rolls.iter().try_fold(0, |idx, &roll| {
+            if !(1..=DIE_SIDES as u8).contains(&roll) {
+                return Err(DicewareError::InvalidRoll(roll));
+            }
+            Ok(idx * DIE_SIDES + (roll - 1) as usize)
+        })
+    }
+
+    /// Get the index of the word for a sequence of dice rolls written as a string, like `"11111"`
+    pub fn index_of_roll_str(&self, rolls: &str) -> Result<usize, DicewareError> {
+        let rolls = rolls
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit @ 1..=6) => Ok(digit as u8),
+                _ => Err(DicewareError::InvalidRollChar(c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.index_of_rolls(&rolls)
+    }
+
+    /// Get the word for a sequence of dice rolls
+    pub fn get(&self, rolls: &[u8]) -> Result<&'t W, DicewareError> {
+        let idx = self.index_of_rolls(rolls)?;
+        Ok(self.tree.get_by_index(idx).expect("index within tree"))
+    }
+
+    /// Get the word for a sequence of dice rolls written as a string, like `"11111"`
+    pub fn get_by_roll_str(&self, rolls: &str) -> Result<&'t W, DicewareError> {
+        let idx = self.index_of_roll_str(rolls)?;
+        Ok(self.tree.get_by_index(idx).expect("index within tree"))
+    }
+
+    /// Get the sequence of dice rolls for the word at index `idx`
+    ///
+    /// Returns `None` if the index is out of range.
+    pub fn rolls_of_index(&self, mut idx: usize) -> Option<Vec<u8>> {
+        if idx >= self.tree.len() {
+            return None;
+        }
+        let mut rolls = vec![0; self.num_dice];
+        for roll in rolls.iter_mut().rev() {
+            *roll = (idx % DIE_SIDES) as u8 + 1;
+            idx /= DIE_SIDES;
+        }
+        Some(rolls)
+    }
+
+    /// Get the sequence of dice rolls for a word
+    ///
+    /// Returns `None` if the word is not in the tree.
+    pub fn rolls_of(&self, word: &str) -> Option<Vec<u8>> {
+        self.rolls_of_index(self.tree.get_index(word)?)
+    }
+}
+
+/// Error returned by [`Diceware`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DicewareError {
+    /// The number of words in the tree is not 6<sup>k</sup> for any k ≥ 1
+    NotAPowerOfSix { len: usize },
+    /// The sequence of dice rolls does not have one roll for each die
+    WrongNumberOfDice { expected: usize, found: usize },
+    /// A roll is not from 1 to 6
+    InvalidRoll(u8),
+    /// A char of a roll string is not a digit from 1 to 6
+    InvalidRollChar(char),
+}
+
+impl fmt::Display for DicewareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAPowerOfSix { len } => {
+                write!(f, "{len} words is not a power of six")
+            }
+            Self::WrongNumberOfDice { expected, found } => {
+                write!(f, "expected {expected} dice rolls, found {found}")
+            }
+            Self::InvalidRoll(roll) => write!(f, "invalid dice roll {roll}"),
+            Self::InvalidRollChar(c) => write!(f, "invalid dice roll {c:?}"),
+        }
+    }
+}
+
+impl std::error::Error for DicewareError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    /// Create a tree of all words made of two letters from `a` to `f`
+    fn example_tree() -> WordCharTreeRootNode<'static, String> {
+        let letters = ['a', 'b', 'c', 'd', 'e', 'f'];
+        letters
+            .iter()
+            .flat_map(|&c1| letters.iter().map(move |&c2| format!("{c1}{c2}")))
+            .map(|word| (word.clone(), word))
+            .collect()
+    }
+
+    #[test_case(&[1, 1], "aa")]
+    #[test_case(&[1, 2], "ab")]
+    #[test_case(&[2, 1], "ba")]
+    #[test_case(&[6, 6], "ff")]
+    fn test_positive_get(rolls: &[u8], expected: &str) {
+        let tree = example_tree();
+        let diceware = Diceware::new(&tree).unwrap();
+        assert_eq!(diceware.num_dice(), 2);
+        assert_eq!(diceware.get(rolls).unwrap(), expected);
+        assert_eq!(diceware.rolls_of(expected).as_deref(), Some(rolls));
+    }
+
+    #[test]
+    fn test_positive_get_by_roll_str() {
+        let tree = example_tree();
+        let diceware = Diceware::new(&tree).unwrap();
+        assert_eq!(diceware.index_of_roll_str("35"), Ok(16));
+        assert_eq!(diceware.get_by_roll_str("35").unwrap(), "ce");
+    }
+
+    #[test_case(&[1], DicewareError::WrongNumberOfDice { expected: 2, found: 1 })]
+    #[test_case(&[1, 1, 1], DicewareError::WrongNumberOfDice { expected: 2, found: 3 })]
+    #[test_case(&[0, 1], DicewareError::InvalidRoll(0))]
+    #[test_case(&[1, 7], DicewareError::InvalidRoll(7))]
+    fn test_negative_get(rolls: &[u8], expected: DicewareError) {
+        let tree = example_tree();
+        let diceware = Diceware::new(&tree).unwrap();
+        assert_eq!(diceware.get(rolls), Err(expected));
+    }
+
+    #[test_case("1a", DicewareError::InvalidRollChar('a'))]
+    #[test_case("70", DicewareError::InvalidRollChar('7'))]
+    fn test_negative_get_by_roll_str(rolls: &str, expected: DicewareError) {
+        let tree = example_tree();
+        let diceware = Diceware::new(&tree).unwrap();
+        assert_eq!(diceware.get_by_roll_str(rolls), Err(expected));
+    }
+
+    #[test_case(0)]
+    #[test_case(1)]
+    #[test_case(35)]
+    #[test_case(216 + 1)]
+    fn test_negative_new_not_a_power_of_six(len: usize) {
+        let tree: WordCharTreeRootNode<usize> = (0..len).map(|i| (format!("{i:04}"), i)).collect();
+        let Err(err) = Diceware::new(&tree) else {
+            panic!("tree of {len} words was accepted");
+        };
+        assert_eq!(err, DicewareError::NotAPowerOfSix { len });
+    }
+
+    #[test_case(6, 1)]
+    #[test_case(216, 3)]
+    fn test_positive_new(len: usize, num_dice: usize) {
+        let tree: WordCharTreeRootNode<usize> = (0..len).map(|i| (format!("{i:04}"), i)).collect();
+        let diceware = Diceware::new(&tree).unwrap();
+        assert_eq!(diceware.num_dice(), num_dice);
+        assert_eq!(diceware.rolls_of_index(len - 1), Some(vec![6; num_dice]));
+        assert_eq!(diceware.rolls_of_index(len), None);
+    }
+}
@@ -0,0 +1,615 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::mem;
+
+use crate::WordCharTreeRootNode;
+
+/// Number of words in a BIP-39 wordlist
+const BIP39_NUM_WORDS: usize = 2048;
+
+/// Number of leading chars that uniquely identify each word in a BIP-39 wordlist
+const BIP39_UNIQUE_PREFIX_LEN: usize = 4;
+
+/// Format of a wordlist file
+///
+/// In every format, leading and trailing whitespace, blank lines, lines starting with `#`,
+/// a leading byte order mark, and CRLF line endings are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordlistFormat {
+    /// One word per line. The index of each word is its position in the list.
+    Plain,
+    /// A Diceware list, such as the EFF lists, with a dice roll sequence before each word.
+    ///
+    /// ```text
+    /// 11111 abacus
+    /// 11112 abdomen
+    /// ```
+    ///
+    /// The index of each word is the dice roll sequence read as a base-6 number,
+    /// where each die shows 1 to 6.
+    Diceware,
+    /// A BIP-39 list of exactly 2048 lowercase ASCII words, one per line,
+    /// each uniquely identified by its first four letters
+    Bip39,
+    /// Comma-separated values, with optional double-quoted fields.
+    ///
+    /// If the first row names a `word` column, it is a header, and the index is
+    /// taken from the `index` column if there is one.
+    /// Otherwise, the word is in the first column, unless the first column of the first row
+    /// is a number, in which case the index is in the first column and the word in the second.
+    /// Any other columns are ignored.
+    Csv,
+}
+
+impl WordlistFormat {
+    /// Detect the format of a wordlist from its contents
+    ///
+    /// Inputs without any sign of another format are [`Self::Plain`].
+    pub fn detect(input: &str) -> Self {
+        let mut lines = content_lines(input).map(|(_, line)| line);
+        let Some(first) = lines.next() else {
+            return Self::Plain;
+        };
+        if split_dice_rolls(first).is_some() {
+            Self::Diceware
+        } else if first.contains(',') {
+            Self::Csv
+        } else if is_bip39(content_lines(input).map(|(_, line)| line)) {
+            Self::Bip39
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+impl fmt::Display for WordlistFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Plain => "plain",
+            Self::Diceware => "Diceware",
+            Self::Bip39 => "BIP-39",
+            Self::Csv => "CSV",
+        })
+    }
+}
+
+/// A word read from a wordlist file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordlistRecord {
+    /// The index of the word in the list
+    pub index: usize,
+    /// The word itself, as written in the file
+    pub word: String,
+    /// The line number of the word in the file, starting from 1
+    pub line: usize,
+}
+
+/// A wordlist read from a file, with its records in the order of the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedWordlist {
+    /// The format that the wordlist was parsed as
+    pub format: WordlistFormat,
+    /// The words of the wordlist, with their index and line number
+    pub records: Vec<WordlistRecord>,
+}
+
+impl ParsedWordlist {
+    /// Parse a wordlist, detecting its format with [`WordlistFormat::detect`]
+    pub fn parse(input: &str) -> Result<Self, ParseWordlistError> {
+        Self::parse_as(input, WordlistFormat::detect(input))
+    }
+
+    /// Parse a wordlist of the given format
+    pub fn parse_as(input: &str, format: WordlistFormat) -> Result<Self, ParseWordlistError> {
+        let records = match format {
+            WordlistFormat::Plain => parse_plain(input)?,
+            WordlistFormat::Diceware => parse_diceware(input)?,
+            WordlistFormat::Bip39 => parse_bip39(input)?,
+            WordlistFormat::Csv => parse_csv(input)?,
+        };
+        check_unique_indices(&records)?;
+        Ok(Self { format, records })
+    }
+
+    /// Get the number of words
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if the wordlist has no words
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Get the words in the order of the file
+    pub fn words(&self) -> impl Iterator<Item = &str> + '_ {
+        self.records.iter().map(|record| record.word.as_str())
+    }
+
+    /// Build a word char tree of the words
+    ///
+    /// The tree orders words alphabetically, so the index of a word in the tree
+    /// only matches its index in the file if the file is sorted.
+    pub fn to_tree<'a>(&self) -> WordCharTreeRootNode<'a, String> {
+        self.words().map(|word| (word, word.to_string())).collect()
+    }
+}
+
+/// Error returned when parsing a wordlist fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWordlistError {
+    /// The line number where the error was found, starting from 1
+    ///
+    /// Errors about the list as a whole refer to the line after the last line.
+    pub line: usize,
+    /// What is wrong with the line
+    pub kind: ParseWordlistErrorKind,
+}
+
+/// The kind of a [`ParseWordlistError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWordlistErrorKind {
+    /// A line holds more than one word
+    UnexpectedWhitespace,
+    /// A Diceware line does not start with a dice roll sequence followed by a word
+    InvalidDiceRolls,
+    /// A dice roll sequence has a different number of dice than the first one
+    InconsistentDiceCount { expected: usize, found: usize },
+    /// A BIP-39 word is not made of lowercase ASCII letters
+    InvalidBip39Word,
+    /// A BIP-39 word starts with the same four letters as the word on an earlier line
+    DuplicateBip39Prefix { first_line: usize },
+    /// A BIP-39 list does not have exactly 2048 words
+    WrongBip39WordCount(usize),
+    /// A CSV field has an opening double quote without a closing one
+    UnterminatedQuote,
+    /// A CSV row has no field for the word, or the field is empty
+    MissingWord,
+    /// A CSV index field is not a number
+    InvalidIndex,
+    /// The index is the same as that of the word on an earlier line
+    DuplicateIndex { first_line: usize },
+}
+
+impl fmt::Display for ParseWordlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ParseWordlistErrorKind::UnexpectedWhitespace => {
+                write!(f, "expected a single word")
+            }
+            ParseWordlistErrorKind::InvalidDiceRolls => {
+                write!(f, "expected dice rolls followed by a word")
+            }
+            ParseWordlistErrorKind::InconsistentDiceCount { expected, found } => {
+                write!(f, "expected {expected} dice rolls, found {found}")
+            }
+            ParseWordlistErrorKind::InvalidBip39Word => {
+                write!(f, "BIP-39 words must be lowercase ASCII letters")
+            }
+            ParseWordlistErrorKind::DuplicateBip39Prefix { first_line } => write!(
+                f,
+                "word has the same first {BIP39_UNIQUE_PREFIX_LEN} letters as the word on line {first_line}"
+            ),
+            ParseWordlistErrorKind::WrongBip39WordCount(count) => {
+                write!(f, "expected {BIP39_NUM_WORDS} BIP-39 words, found {count}")
+            }
+            ParseWordlistErrorKind::UnterminatedQuote => write!(f, "unterminated quoted field"),
+            ParseWordlistErrorKind::MissingWord => write!(f, "missing word"),
+            ParseWordlistErrorKind::InvalidIndex => write!(f, "index is not a number"),
+            ParseWordlistErrorKind::DuplicateIndex { first_line } => {
+                write!(f, "same index as the word on line {first_line}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseWordlistError {}
+
+/// Iterate over the line numbers and trimmed contents of the lines
+/// that are not blank and not comments
+fn content_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Get the line number after the last line
+fn end_line(input: &str) -> usize {
+    input.lines().count() + 1
+}
+
+fn single_word(line_no: usize, word: &str) -> Result<String, ParseWordlistError> {
+    if word.contains(char::is_whitespace) {
+        return Err(ParseWordlistError {
+            line: line_no,
+            kind: ParseWordlistErrorKind::UnexpectedWhitespace,
+        });
+    }
+    Ok(word.to_string())
+}
+
+fn parse_plain(input: &str) -> Result<Vec<WordlistRecord>, ParseWordlistError> {
+    content_lines(input)
+        .enumerate()
+        .map(|(index, (line_no, line))| {
+            Ok(WordlistRecord {
+                index,
+                word: single_word(line_no, line)?,
+                line: line_no,
+            })
+        })
+        .collect()
+}
+
+/// Split a Diceware line into its dice roll sequence and the rest of the line
+fn split_dice_rolls(line: &str) -> Option<(&str, &str)> {
+    let (dice_rolls, rest) = line.split_once(|c: char| c.is_whitespace())?;
+    let rest = rest.trim_start();
+    let is_dice_rolls = dice_rolls.bytes().all(|b| (b'1'..=b'6').contains(&b));
+    (is_dice_rolls && !rest.is_empty()).then_some((dice_rolls, rest))
+}
+
+fn parse_diceware(input: &str) -> Result<Vec<WordlistRecord>, ParseWordlistError> {
+    let mut num_dice = None;
+    content_lines(input)
+        .map(|(line_no, line)| {
+            let error = |kind| ParseWordlistError {
+                line: line_no,
+                kind,
+            };
+            let (dice_rolls, word) =
+                split_dice_rolls(line).ok_or(error(ParseWordlistErrorKind::InvalidDiceRolls))?;
+            let expected = *num_dice.get_or_insert(dice_rolls.len());
+            if dice_rolls.len() != expected {
+                return Err(error(ParseWordlistErrorKind::InconsistentDiceCount {
+                    expected,
+                    found: dice_rolls.len(),
+                }));
+            }
+            let index = dice_rolls
+                .bytes()
+                .try_fold(0usize, |index, b| {
+                    index.checked_mul(6)?.checked_add((b - b'1') as usize)
+                })
+                .ok_or(error(ParseWordlistErrorKind::InvalidDiceRolls))?;
+            Ok(WordlistRecord {
+                index,
+                word: single_word(line_no, word)?,
+                line: line_no,
+            })
+        })
+        .collect()
+}
+
+fn is_bip39<'i>(mut lines: impl Iterator<Item = &'i str>) -> bool {
+    let mut prefixes = HashSet::new();
+    let mut count = 0;
+    lines.all(|word| {
+        count += 1;
+        is_bip39_word(word) && prefixes.insert(bip39_prefix(word))
+    }) && count == BIP39_NUM_WORDS
+}
+
+fn is_bip39_word(word: &str) -> bool {
+    !word.is_empty() && word.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+fn bip39_prefix(word: &str) -> &str {
+    &word[..word.len().min(BIP39_UNIQUE_PREFIX_LEN)]
+}
+
+fn parse_bip39(input: &str) -> Result<Vec<WordlistRecord>, ParseWordlistError> {
+    let records = parse_plain(input)?;
+    let mut prefixes = HashSet::new();
+    for record in &records {
+        let error = |kind| ParseWordlistError {
+            line: record.line,
+            kind,
+        };
+        if !is_bip39_word(&record.word) {
+            return Err(error(ParseWordlistErrorKind::InvalidBip39Word));
+        }
+        let prefix = bip39_prefix(&record.word);
+        if !prefixes.insert(prefix) {
+            let first_line = first_line_of(&records, |first| bip39_prefix(&first.word) == prefix);
+            return Err(error(ParseWordlistErrorKind::DuplicateBip39Prefix {
+                first_line,
+            }));
+        }
+    }
+    if records.len() != BIP39_NUM_WORDS {
+        return Err(ParseWordlistError {
+            line: end_line(input),
+            kind: ParseWordlistErrorKind::WrongBip39WordCount(records.len()),
+        });
+    }
+    Ok(records)
+}
+
+/// Split a CSV row into its fields, removing the quotes around quoted fields
+fn split_csv_row(row: &str) -> Option<Vec<String>> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => fields.push(mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    fields.push(field.trim().to_string());
+    Some(fields)
+}
+
+fn parse_csv(input: &str) -> Result<Vec<WordlistRecord>, ParseWordlistError> {
+    let mut rows = content_lines(input).map(|(line_no, line)| {
+        split_csv_row(line)
+            .map(|fields| (line_no, fields))
+            .ok_or(ParseWordlistError {
+                line: line_no,
+                kind: ParseWordlistErrorKind::UnterminatedQuote,
+            })
+    });
+    let Some(first) = rows.next() else {
+        return Ok(vec![]);
+    };
+    let (first_line_no, first_fields) = first?;
+    let column = |name: &str| {
+        first_fields
+            .iter()
+            .position(|field| field.eq_ignore_ascii_case(name))
+    };
+    let (word_column, index_column, first_row) = match column("word") {
+        Some(word_column) => (word_column, column("index"), None),
+        None if first_fields.len() > 1 && first_fields[0].parse::<usize>().is_ok() => {
+            (1, Some(0), Some((first_line_no, first_fields)))
+        }
+        None => (0, None, Some((first_line_no, first_fields))),
+    };
+    first_row
+        .map(Ok)
+        .into_iter()
+        .chain(rows)
+        .enumerate()
+        .map(|(position, row)| {
+            let (line_no, fields) = row?;
+            let error = |kind| ParseWordlistError {
+                line: line_no,
+                kind,
+            };
+            let word = fields
+                .get(word_column)
+                .filter(|word| !word.is_empty())
+                .ok_or(error(ParseWordlistErrorKind::MissingWord))?;
+            let index = match index_column {
+                Some(index_column) => fields
+                    .get(index_column)
+                    .and_then(|index| index.parse().ok())
+                    .ok_or(error(ParseWordlistErrorKind::InvalidIndex))?,
+                None => position,
+            };
+            Ok(WordlistRecord {
+                index,
+                word: single_word(line_no, word)?,
+                line: line_no,
+            })
+        })
+        .collect()
+}
+
+fn check_unique_indices(records: &[WordlistRecord]) -> Result<(), ParseWordlistError> {
+    let mut indices = HashSet::with_capacity(records.len());
+    for record in records {
+        if !indices.insert(record.index) {
+            let first_line = first_line_of(records, |first| first.index == record.index);
+            return Err(ParseWordlistError {
+                line: record.line,
+                kind: ParseWordlistErrorKind::DuplicateIndex { first_line },
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Get the line of the first record that matches, to report where a duplicate was first seen
+fn first_line_of(records: &[WordlistRecord], matches: impl Fn(&WordlistRecord) -> bool) -> usize {
+    records
+        .iter()
+        .find(|record| matches(record))
+        .expect("a duplicate has an earlier record")
+        .line
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    /// Generate a valid BIP-39 list from four-letter words
+    fn example_bip39_list() -> String {
+        (0..BIP39_NUM_WORDS)
+            .map(|i| {
+                let letters = [i / (26 * 26 * 26), i / (26 * 26), i / 26, i];
+                let word = letters
+                    .map(|n| char::from(b'a' + (n % 26) as u8))
+                    .iter()
+                    .collect::<String>();
+                format!("{word}\n")
+            })
+            .collect()
+    }
+
+    #[test_case("", WordlistFormat::Plain)]
+    #[test_case("arm\narmy\nman\n", WordlistFormat::Plain)]
+    #[test_case("11111\tabacus\n11112\tabdomen\n", WordlistFormat::Diceware)]
+    #[test_case("# EFF list\n1111 aardvark\n", WordlistFormat::Diceware)]
+    #[test_case("word,frequency\narm,10\n", WordlistFormat::Csv)]
+    #[test_case("\u{feff}0,arm\r\n1,army\r\n", WordlistFormat::Csv)]
+    fn test_detect_format(input: &str, expected: WordlistFormat) {
+        assert_eq!(WordlistFormat::detect(input), expected);
+    }
+
+    #[test]
+    fn test_detect_bip39() {
+        let list = example_bip39_list();
+        assert_eq!(WordlistFormat::detect(&list), WordlistFormat::Bip39);
+        let parsed = ParsedWordlist::parse(&list).unwrap();
+        assert_eq!(parsed.len(), BIP39_NUM_WORDS);
+        assert_eq!(parsed.records[1].word, "aaab");
+        // Plain lists of any other length are not BIP-39 lists.
+        let shorter = list.lines().skip(1).collect::<Vec<_>>().join("\n");
+        assert_eq!(WordlistFormat::detect(&shorter), WordlistFormat::Plain);
+    }
+
+    #[test]
+    fn test_parse_plain_skips_comments_blank_lines_bom_and_crlf() {
+        let parsed =
+            ParsedWordlist::parse("\u{feff}# words\r\narm\r\n\r\n  army  \r\nman").unwrap();
+        assert_eq!(parsed.format, WordlistFormat::Plain);
+        assert_eq!(
+            parsed.records,
+            vec![
+                WordlistRecord {
+                    index: 0,
+                    word: "arm".to_string(),
+                    line: 2
+                },
+                WordlistRecord {
+                    index: 1,
+                    word: "army".to_string(),
+                    line: 4
+                },
+                WordlistRecord {
+                    index: 2,
+                    word: "man".to_string(),
+                    line: 5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_diceware_indices() {
+        let parsed =
+            ParsedWordlist::parse("11111\tabacus\n11112\tabdomen\n11121 abdominal\n66666\tzoom\n")
+                .unwrap();
+        assert_eq!(parsed.format, WordlistFormat::Diceware);
+        assert_eq!(
+            parsed
+                .records
+                .iter()
+                .map(|record| (record.index, record.word.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "abacus"),
+                (1, "abdomen"),
+                (6, "abdominal"),
+                (7775, "zoom")
+            ]
+        );
+    }
+
+    #[test_case("word,index,frequency\narm,5,10\n\"army\",6,3\n", &[(5, "arm"), (6, "army")] ; "header")]
+    #[test_case("3,arm,x\n4, \"ar\"\"my\" ,y\n", &[(3, "arm"), (4, "ar\"my")] ; "index column")]
+    #[test_case("arm,noun\nman,\"noun, verb\"\n", &[(0, "arm"), (1, "man")] ; "word column")]
+    fn test_parse_csv(input: &str, expected: &[(usize, &str)]) {
+        let parsed = ParsedWordlist::parse_as(input, WordlistFormat::Csv).unwrap();
+        assert_eq!(
+            parsed
+                .records
+                .iter()
+                .map(|record| (record.index, record.word.as_str()))
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test_case(
+        "arm\nbig arm\n",
+        WordlistFormat::Plain,
+        2,
+        ParseWordlistErrorKind::UnexpectedWhitespace
+    )]
+    #[test_case(
+        "11111\tabacus\nabdomen\n",
+        WordlistFormat::Diceware,
+        2,
+        ParseWordlistErrorKind::InvalidDiceRolls
+    )]
+    #[test_case("11111\tabacus\n# comment\n1112\tabdomen\n", WordlistFormat::Diceware, 3, ParseWordlistErrorKind::InconsistentDiceCount { expected: 5, found: 4 })]
+    #[test_case("11111\tabacus\n11111\tabdomen\n", WordlistFormat::Diceware, 2, ParseWordlistErrorKind::DuplicateIndex { first_line: 1 })]
+    #[test_case(
+        "able\nAbout\n",
+        WordlistFormat::Bip39,
+        2,
+        ParseWordlistErrorKind::InvalidBip39Word
+    )]
+    #[test_case("abandon\nabandoned\n", WordlistFormat::Bip39, 2, ParseWordlistErrorKind::DuplicateBip39Prefix { first_line: 1 })]
+    #[test_case(
+        "abandon\nability\n",
+        WordlistFormat::Bip39,
+        3,
+        ParseWordlistErrorKind::WrongBip39WordCount(2)
+    )]
+    #[test_case(
+        "word\narm\n\"army\n",
+        WordlistFormat::Csv,
+        3,
+        ParseWordlistErrorKind::UnterminatedQuote
+    )]
+    #[test_case(
+        "index,word\n0,arm\n1,\n",
+        WordlistFormat::Csv,
+        3,
+        ParseWordlistErrorKind::MissingWord
+    )]
+    #[test_case(
+        "index,word\nzero,arm\n",
+        WordlistFormat::Csv,
+        2,
+        ParseWordlistErrorKind::InvalidIndex
+    )]
+    fn test_negative_parse(
+        input: &str,
+        format: WordlistFormat,
+        line: usize,
+        kind: ParseWordlistErrorKind,
+    ) {
+        assert_eq!(
+            ParsedWordlist::parse_as(input, format),
+            Err(ParseWordlistError { line, kind })
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = ParsedWordlist::parse("11111\tabacus\n1112\tabdomen\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected 5 dice rolls, found 4");
+    }
+
+    #[test]
+    fn test_to_tree() {
+        let parsed = ParsedWordlist::parse("11111\tarmy\n11112\tarm\n11113\tman\n").unwrap();
+        let tree = parsed.to_tree();
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.get_index("arm"), Some(0));
+        assert!(tree.is_fully_well_formed());
+    }
+}
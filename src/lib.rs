@@ -1,5 +1,27 @@
+// Tests link to std even without the `std` feature, so that they can also cover
+// the code paths for targets without std.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "bip39")]
+mod bip39;
+#[cfg(feature = "std")]
+mod codec;
+#[cfg(feature = "std")]
+mod diceware;
+#[cfg(feature = "std")]
+mod formats;
 mod tree;
+#[cfg(feature = "std")]
 mod words;
 
+#[cfg(feature = "bip39")]
+pub use bip39::*;
+#[cfg(feature = "std")]
+pub use codec::*;
+#[cfg(feature = "std")]
+pub use diceware::*;
+#[cfg(feature = "std")]
+pub use formats::*;
 pub use tree::*;
+#[cfg(feature = "std")]
 pub use words::*;
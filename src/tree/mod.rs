@@ -1,3 +1,12 @@
+#[cfg(feature = "std")]
+mod phrase;
+mod word;
 mod word_char;
 
+#[cfg(feature = "std")]
+pub use phrase::*;
+pub use word::*;
 pub use word_char::*;
+
+#[cfg(all(test, feature = "std"))]
+pub(crate) use word_char::test as word_char_test;
@@ -0,0 +1,492 @@
+use std::collections::vec_deque::{Drain, IntoIter};
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Range;
+use std::str::Chars;
+
+use crate::{NodeRef, WordCharTreeRootNode, WordTree, WordTreeBuilder, WordTreeWords};
+
+/// A tree of phrases, where the edges are the words `W` of a [`WordCharTreeRootNode`]
+/// and the nodes are `Option<P>` phrases
+///
+/// Each edge is a word of the word char tree, identified by its index in the order of
+/// [`WordCharTreeRootNode::words`], so that a phrase such as `"ice cream"` is stored under
+/// the indices of `ice` and of `cream`. The phrase tree is a [`WordTree`] with these labels.
+///
+/// Words are separated by whitespace in the phrases that the tree is created from.
+/// In the text that is searched for phrases, words are found by a char search over
+/// the word char tree, see [`PhraseStream`].
+pub struct PhraseTree<'t, 'a, W, P> {
+    words: &'t WordCharTreeRootNode<'a, W>,
+    tree: WordTree<'t, usize, P>,
+}
+
+/// A phrase found in a text by [`PhraseTree::longest_match`] or [`PhraseTree::find_phrases`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhraseMatch<'t, P> {
+    /// The phrase `P` that was found
+    pub phrase: &'t P,
+    /// The byte range of the phrase in the text, from the start of its first word
+    /// to the end of its last word
+    pub span: Range<usize>,
+    /// The number of words in the phrase
+    pub num_words: usize,
+}
+
+impl<'t, 'a, W, P> PhraseTree<'t, 'a, W, P> {
+    /// Create a phrase tree over the words of a word char tree
+    ///
+    /// Every word of every phrase must be a word of the word char tree.
+    /// Phrases without any words are ignored.
+    pub fn new<S: AsRef<str>>(
+        words: &'t WordCharTreeRootNode<'a, W>,
+        phrases: impl IntoIterator<Item = (S, P)>,
+    ) -> Result<Self, PhraseTreeError> {
+        let mut builder = WordTreeBuilder::new();
+        for (phrase, value) in phrases {
+            let phrase = phrase.as_ref();
+            let indices = phrase
+                .split_whitespace()
+                .enumerate()
+                .map(|(position, word)| {
+                    words
+                        .get_index(word)
+                        .ok_or_else(|| PhraseTreeError::UnknownWord {
+                            phrase: phrase.to_string(),
+                            position,
+                            word: word.to_string(),
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder.insert(indices, value);
+        }
+        Ok(Self {
+            words,
+            tree: builder.build(),
+        })
+    }
+    /// Get the word char tree whose words are the edges of the phrase tree
+    pub fn word_tree(&self) -> &'t WordCharTreeRootNode<'a, W> {
+        self.words
+    }
+    /// Get the phrase tree as a [`WordTree`] labeled by word index
+    pub fn as_word_tree(&self) -> &WordTree<'t, usize, P> {
+        &self.tree
+    }
+    /// Get the number of words in the longest phrase
+    pub fn get_max_depth(&self) -> usize {
+        self.tree.get_max_depth()
+    }
+    /// Returns an iterator over the phrases `P`, in the order of the indices of their words
+    pub fn phrases(&self) -> WordTreeWords<'_, 't, usize, P> {
+        self.tree.words()
+    }
+    /// Get the number of phrases in the tree
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+    /// Returns `true` if the tree contains no phrases
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+    /// Look up the phrase `P` for the given words, separated by whitespace
+    pub fn get(&self, phrase: &str) -> Option<&P> {
+        let indices = phrase
+            .split_whitespace()
+            .map(|word| self.words.get_index(word))
+            .collect::<Option<Vec<_>>>()?;
+        self.tree.get(indices)
+    }
+    /// Find the longest phrase that starts at the first word of the text
+    ///
+    /// Same as the first phrase found by [`Self::find_phrases`], as long as it starts
+    /// at the first char of the text that is not whitespace.
+    pub fn longest_match(&self, text: &str) -> Option<PhraseMatch<'_, P>> {
+        let start = text.find(|c: char| !c.is_whitespace())?;
+        self.find_phrases(text)
+            .next()
+            .filter(|found| found.span.start == start)
+    }
+    /// Returns an iterator over the phrases found in the text
+    ///
+    /// The text is scanned from start to end, and at each word the longest phrase
+    /// that starts there is reported. The scan then continues after the end of that phrase,
+    /// so that the matches do not overlap. See [`PhraseStream`] for how words are found.
+    pub fn find_phrases<'s>(&self, text: &'s str) -> PhraseMatches<'_, 's, 't, 'a, W, P> {
+        PhraseMatches {
+            stream: self.stream(),
+            chars: Some(text.chars()),
+        }
+    }
+    /// Start a search for phrases in a text that is given piece by piece
+    pub fn stream(&self) -> PhraseStream<'_, 't, 'a, W, P> {
+        PhraseStream {
+            phrase_tree: self,
+            pos: 0,
+            word_start: None,
+            node: None,
+            segment: String::new(),
+            words: VecDeque::new(),
+            found: VecDeque::new(),
+        }
+    }
+}
+
+/// An iterator over the phrases found in a text, returned by [`PhraseTree::find_phrases`]
+pub struct PhraseMatches<'p, 's, 't, 'a, W, P> {
+    stream: PhraseStream<'p, 't, 'a, W, P>,
+    /// The chars of the text that are left, or `None` once the end of the text is handled
+    chars: Option<Chars<'s>>,
+}
+
+impl<'p, W, P> Iterator for PhraseMatches<'p, '_, '_, '_, W, P> {
+    type Item = PhraseMatch<'p, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.stream.found.pop_front() {
+                return Some(found);
+            }
+            match self.chars.as_mut()?.next() {
+                Some(c) => self.stream.push_char(c),
+                None => {
+                    self.stream.end();
+                    self.chars = None;
+                }
+            }
+        }
+    }
+}
+
+/// A search for phrases in a text that is given piece by piece,
+/// returned by [`PhraseTree::stream`]
+///
+/// The search is a char search over the word char tree, to spot words, chained into
+/// a word search over the phrase tree, to spot phrases:
+/// - Each [`char`] of the text is mapped by the case folding policy and the normalization
+///   of the word char tree, and the edge for it is followed from the node of the word so far.
+/// - A word ends at whitespace, or at any other char that is not alphanumeric and
+///   that does not continue the word in the tree, such as punctuation.
+///   Such chars also end the phrase, so that `"new, york"` does not match `"new york"`.
+/// - Each word that ends at a node with a word `W` is fed into the search of the phrase tree.
+///
+/// A phrase is reported once no longer phrase can start at the same word, which may take
+/// some more pieces of text. The spans of the phrases are byte ranges in all the text
+/// that has been given so far.
+pub struct PhraseStream<'p, 't, 'a, W, P> {
+    phrase_tree: &'p PhraseTree<'t, 'a, W, P>,
+    /// The number of bytes of text given so far
+    pos: usize,
+    /// The start of the current word, if any
+    word_start: Option<usize>,
+    /// The node of the word char tree for the current word, together with the start of
+    /// the `idx_range` of the edge to it, or `None` if no word of the tree matches the word
+    node: Option<(usize, NodeRef<'t, 'a, char, W>)>,
+    /// The last char of the current word, and the combining marks after it,
+    /// which are mapped together once the next char that is not a combining mark is given
+    segment: String,
+    /// The words from the start of the longest phrase in progress, with their word index
+    words: VecDeque<(Range<usize>, Option<usize>)>,
+    /// The phrases found, and not yet returned
+    found: VecDeque<PhraseMatch<'p, P>>,
+}
+
+impl<'p, 't, 'a, W, P> PhraseStream<'p, 't, 'a, W, P> {
+    /// Give the next piece of text to the search,
+    /// and return the phrases that are found with it
+    pub fn push_str(&mut self, text: &str) -> Drain<'_, PhraseMatch<'p, P>> {
+        for c in text.chars() {
+            self.push_char(c);
+        }
+        self.found.drain(..)
+    }
+    /// End the text, and return the phrases that are found at its end
+    pub fn finish(mut self) -> IntoIter<PhraseMatch<'p, P>> {
+        self.end();
+        self.found.into_iter()
+    }
+    fn push_char(&mut self, c: char) {
+        if self.word_start.is_some() && is_combining_mark(c) {
+            self.segment.push(c);
+            self.pos += c.len_utf8();
+            return;
+        }
+        self.map_segment();
+        if c.is_whitespace() {
+            self.end_word();
+        } else if c.is_alphanumeric() || self.continues_word(c) {
+            if self.word_start.is_none() {
+                self.word_start = Some(self.pos);
+                self.node = Some((0, self.phrase_tree.words.root()));
+            }
+            self.segment.push(c);
+        } else {
+            self.end_word();
+            // A char that ends a word without being whitespace also ends the phrase.
+            self.push_word(self.pos..self.pos + c.len_utf8(), None);
+        }
+        self.pos += c.len_utf8();
+    }
+    /// Follow the edges for the mapped chars of the segment
+    fn map_segment(&mut self) {
+        if let Some(node) = self.node {
+            self.node = self.follow(node, &self.segment);
+        }
+        self.segment.clear();
+    }
+    /// Returns `true` if there is an edge for the mapped char, from the node of the current
+    /// word, or from the root node if there is no current word
+    fn continues_word(&self, c: char) -> bool {
+        let node = match self.word_start {
+            Some(_) => self.node,
+            None => Some((0, self.phrase_tree.words.root())),
+        };
+        node.and_then(|node| self.follow(node, c.encode_utf8(&mut [0; 4])))
+            .is_some()
+    }
+    fn follow(
+        &self,
+        node: (usize, NodeRef<'t, 'a, char, W>),
+        s: &str,
+    ) -> Option<(usize, NodeRef<'t, 'a, char, W>)> {
+        let words = self.phrase_tree.words;
+        let mut chars = words.edge_chars(s);
+        chars.try_fold(node, |(_, node), c| {
+            node.children()
+                .find(|(label, _, _)| **label == c)
+                .map(|(_, idx_range, child)| (*idx_range.start(), child))
+        })
+    }
+    fn end_word(&mut self) {
+        let Some(start) = self.word_start.take() else {
+            return;
+        };
+        self.map_segment();
+        // The word of a node is the first word in the `idx_range` of the edge to the node.
+        let idx = self
+            .node
+            .take()
+            .and_then(|(idx, node)| node.word().map(|_| idx));
+        self.push_word(start..self.pos, idx);
+    }
+    fn push_word(&mut self, span: Range<usize>, idx: Option<usize>) {
+        self.words.push_back((span, idx));
+        self.report_phrases(false);
+    }
+    fn end(&mut self) {
+        self.end_word();
+        self.report_phrases(true);
+    }
+    /// Report the longest phrase at the first word, and drop the words that it covers,
+    /// or drop the first word if no phrase starts at it, for as long as no more words
+    /// are needed to know what the longest phrase is
+    fn report_phrases(&mut self, at_end: bool) {
+        while let Some((first_span, _)) = self.words.front() {
+            let start = first_span.start;
+            let mut node = self.phrase_tree.tree.root();
+            let mut longest = None;
+            let mut needs_more_words = !at_end;
+            for (num_words, (span, idx)) in (1..).zip(&self.words) {
+                let Some(child) = idx.and_then(|idx| node.child(idx)) else {
+                    needs_more_words = false;
+                    break;
+                };
+                if let Some(phrase) = child.word() {
+                    longest = Some(PhraseMatch {
+                        phrase,
+                        span: start..span.end,
+                        num_words,
+                    });
+                }
+                node = child;
+            }
+            if needs_more_words && !node.is_leaf() {
+                return;
+            }
+            let num_words = longest.as_ref().map_or(1, |found| found.num_words);
+            self.words.drain(..num_words);
+            self.found.extend(longest);
+        }
+    }
+}
+
+/// Returns `true` if the char is normalized together with the char before it
+#[cfg(feature = "normalization")]
+fn is_combining_mark(c: char) -> bool {
+    unicode_normalization::char::is_combining_mark(c)
+}
+
+/// Returns `true` if the char is normalized together with the char before it
+#[cfg(not(feature = "normalization"))]
+fn is_combining_mark(_: char) -> bool {
+    false
+}
+
+/// Error returned by [`PhraseTree::new`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhraseTreeError {
+    /// A word of a phrase is not a word of the word char tree
+    UnknownWord {
+        phrase: String,
+        position: usize,
+        word: String,
+    },
+}
+
+impl fmt::Display for PhraseTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownWord {
+                phrase,
+                position,
+                word,
+            } => write!(
+                f,
+                "unknown word {word:?} at position {position} of phrase {phrase:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PhraseTreeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    fn example_words() -> WordCharTreeRootNode<'static, String> {
+        [
+            "ice", "cream", "new", "york", "city", "times", "square", "i", "like",
+        ]
+        .into_iter()
+        .map(|w| (w, w.to_string()))
+        .collect()
+    }
+
+    fn example_phrases<'t>(
+        words: &'t WordCharTreeRootNode<'static, String>,
+    ) -> PhraseTree<'t, 'static, String, &'static str> {
+        let phrases = [
+            "ice cream",
+            "new york",
+            "new york city",
+            "new york times",
+            "times square",
+        ];
+        PhraseTree::new(words, phrases.map(|p| (p, p))).unwrap()
+    }
+
+    #[test]
+    fn test_positive_get() {
+        let words = example_words();
+        let phrases = example_phrases(&words);
+        assert_eq!(phrases.len(), 5);
+        assert_eq!(phrases.get_max_depth(), 3);
+        assert_eq!(phrases.get("New  York"), Some(&"new york"));
+        assert_eq!(phrases.get("new"), None);
+        assert_eq!(phrases.get("new jersey"), None);
+    }
+
+    #[test_case("new york city at night", Some(("new york city", 0..13, 3)) ; "longest")]
+    #[test_case("  new york times square", Some(("new york times", 2..16, 3)) ; "leading whitespace")]
+    #[test_case("new york", Some(("new york", 0..8, 2)) ; "end of text")]
+    #[test_case("new jersey", None ; "prefix only")]
+    #[test_case("i like ice cream", None ; "not at start")]
+    fn test_longest_match(text: &str, expected: Option<(&str, Range<usize>, usize)>) {
+        let words = example_words();
+        let phrases = example_phrases(&words);
+        let found = phrases.longest_match(text);
+        assert_eq!(found.map(|m| (*m.phrase, m.span, m.num_words)), expected);
+    }
+
+    #[test]
+    fn test_find_phrases() {
+        let words = example_words();
+        let phrases = example_phrases(&words);
+        let text = "I like ice cream in New York Times Square";
+        let found = phrases
+            .find_phrases(text)
+            .map(|m| (*m.phrase, &text[m.span]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                ("ice cream", "ice cream"),
+                ("new york times", "New York Times")
+            ]
+        );
+    }
+
+    #[test_case("ice cream, please", &[("ice cream", 0..9)] ; "punctuation after phrase")]
+    #[test_case("(new york)", &[("new york", 1..9)] ; "punctuation around phrase")]
+    #[test_case("new, york", &[] ; "punctuation inside phrase")]
+    #[test_case("ice creamy", &[] ; "word continues")]
+    #[test_case("new york's times square", &[("new york", 0..8), ("times square", 11..23)] ; "apostrophe")]
+    fn test_find_phrases_word_boundaries(text: &str, expected: &[(&str, Range<usize>)]) {
+        let words = example_words();
+        let phrases = example_phrases(&words);
+        let found = phrases
+            .find_phrases(text)
+            .map(|m| (*m.phrase, m.span))
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_stream_pieces() {
+        let words = example_words();
+        let phrases = example_phrases(&words);
+        let mut stream = phrases.stream();
+        let mut found = vec![];
+        for piece in [
+            "I like ice cr",
+            "eam in New Y",
+            "ork Times Square and New York",
+        ] {
+            found.extend(stream.push_str(piece).map(|m| (*m.phrase, m.span)));
+        }
+        assert_eq!(found, [("ice cream", 7..16), ("new york times", 20..34)]);
+        // "new york" is only known to be the longest phrase once the text ends.
+        found.extend(stream.finish().map(|m| (*m.phrase, m.span)));
+        assert_eq!(found[2..], [("new york", 46..54)]);
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn test_stream_combining_mark_in_next_piece() {
+        use crate::{Normalization, WordCharTreeBuilder};
+
+        let mut builder = WordCharTreeBuilder::new().with_normalization(Normalization::Nfc);
+        builder.insert("crème", ());
+        builder.insert("brûlée", ());
+        let words = builder.build();
+        let phrases = PhraseTree::new(&words, [("crème brûlée", "dessert")]).unwrap();
+        let mut stream = phrases.stream();
+        assert_eq!(stream.push_str("Cre").count(), 0);
+        assert_eq!(stream.push_str("\u{300}me bru\u{302}le").count(), 0);
+        assert_eq!(stream.push_str("\u{301}e").count(), 0);
+        let found = stream.finish().collect::<Vec<_>>();
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            (*found[0].phrase, found[0].span.clone()),
+            ("dessert", 0..18)
+        );
+    }
+
+    #[test]
+    fn test_negative_new_unknown_word() {
+        let words = example_words();
+        let Err(err) = PhraseTree::new(&words, [("ice cream", 0), ("hot dog", 1)]) else {
+            panic!("phrase with unknown words was accepted");
+        };
+        assert_eq!(
+            err,
+            PhraseTreeError::UnknownWord {
+                phrase: "hot dog".to_string(),
+                position: 0,
+                word: "hot".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,484 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::ops::{Deref, RangeInclusive};
+
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod by_len;
+mod cursor;
+mod iter;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "std")]
+mod set_ops;
+#[cfg(feature = "std")]
+mod transform;
+
+#[cfg(feature = "std")]
+pub use builder::*;
+#[cfg(feature = "std")]
+pub use by_len::*;
+pub use cursor::*;
+pub use iter::*;
+#[cfg(feature = "rayon")]
+pub use par::*;
+
+/// The root node of a tree, where the edges are labels `L` and the nodes are `Option<W>` words
+///
+/// Each word is stored under the sequence of labels along the path from the root node
+/// to its node. The edges of every node are sorted by label, so that [`Self::words`] visits
+/// the words in the lexicographic order of their label sequences. The index of a word
+/// is its position in that order.
+///
+/// The labels may for example be bytes, for ASCII-only wordlists and binary keys,
+/// `u16` phoneme IDs, or whole words, for lists of phrases. The word char tree
+/// [`WordCharTreeRootNode`](`crate::WordCharTreeRootNode`) is a `WordTree<'a, char, W>`
+/// together with the mapping of strings to chars.
+///
+/// Regarding the `Option<W>` words in the tree, see in particular the following:
+/// - [`Self::is_fully_well_formed`]
+/// - [`Self::is_suitable_for_iterative_search`]
+/// - [`Self::words`]
+pub struct WordTree<'a, L, W> {
+    pub(crate) edges: Edges<'a, WordTreeEdge<'a, L, W>>,
+}
+
+impl<L, W> Default for WordTree<'_, L, W> {
+    fn default() -> Self {
+        Self {
+            edges: Edges::default(),
+        }
+    }
+}
+
+impl<'a, L: Ord, W> WordTree<'a, L, W> {
+    /// Get the max depth of the tree
+    ///
+    /// Measured in number of label edges from the root node to the deepest node in the tree.
+    ///
+    /// In a [fully well-formed](`Self::is_fully_well_formed`) tree, this depth
+    /// corresponds to the length in labels of the longest word in the tree.
+    pub const fn get_max_depth(&self) -> usize {
+        let edges = self.edges.as_slice();
+        let mut max_depth = 0;
+        let mut i = 0;
+        while i < edges.len() {
+            let depth = edges[i].get_max_depth(0);
+            if depth > max_depth {
+                max_depth = depth;
+            }
+            i += 1;
+        }
+        max_depth
+    }
+    /// The tree is *fully well-formed* as long as either of the following is true:
+    /// - The tree is empty, or
+    /// - every leaf node (node without child edges) corresponds to a word `W`.
+    ///
+    /// Additional notes:
+    /// - Non-leaf nodes are allowed to have `word: None`.
+    /// - Non-leaf nodes are allowed to have `word: Some(W)`.
+    ///
+    /// The tree is NOT *fully well-formed* if any of the leaf nodes have `word: None`.
+    pub const fn is_fully_well_formed(&self) -> bool {
+        let edges = self.edges.as_slice();
+        let mut i = 0;
+        while i < edges.len() {
+            if !edges[i].is_fully_well_formed() {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+    /// The tree is *suitable for iterative search* for words `W` if the following is true:
+    /// - Every non-leaf node has `word: None`.
+    ///
+    /// Additional notes:
+    /// - Leaf nodes are allowed to have `word: None`.
+    /// - Leaf nodes are allowed to have `word: Some(W)`.
+    ///
+    /// In *iterative search*, words are fed into the search one label at a time,
+    /// and the search returns a match as soon as the shortest match is found.
+    /// See [`WordCharTreeRootNode::is_suitable_for_iterative_char_search`](`crate::WordCharTreeRootNode::is_suitable_for_iterative_char_search`)
+    /// for an example.
+    pub const fn is_suitable_for_iterative_search(&self) -> bool {
+        let edges = self.edges.as_slice();
+        let mut i = 0;
+        while i < edges.len() {
+            if !edges[i].is_suitable_for_iterative_search() {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+    /// Returns an iterator over the words `W` of the tree
+    ///
+    /// The words are visited in order, and can also be visited in reverse order
+    /// with [`Iterator::rev`]. The number of words is known from the `idx_range`s.
+    pub fn words(&self) -> WordTreeWords<'_, 'a, L, W> {
+        WordTreeWords::new(&self.edges)
+    }
+    /// Get the number of words in the tree
+    ///
+    /// Computed from the `idx_range`s of the edges of the root node.
+    pub fn len(&self) -> usize {
+        self.edges
+            .iter()
+            .map(|edge| edge.idx_range.end() + 1 - edge.idx_range.start())
+            .sum()
+    }
+    /// Returns `true` if the tree contains no words
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+    /// Get the number of nodes in the tree, including the root node
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .edges
+            .iter()
+            .map(|edge| edge.child_node.node_count())
+            .sum::<usize>()
+    }
+    /// Look up the word `W` for the given sequence of labels
+    pub fn get<K: Borrow<L>>(&self, labels: impl IntoIterator<Item = K>) -> Option<&W> {
+        self.find_edge(labels)
+            .and_then(|edge| edge.child_node.word.as_ref())
+    }
+    /// Look up the index of the word for the given sequence of labels
+    ///
+    /// The index of a word is the position of the word in the order of [`Self::words`].
+    pub fn get_index<K: Borrow<L>>(&self, labels: impl IntoIterator<Item = K>) -> Option<usize> {
+        self.find_edge(labels)
+            .filter(|edge| edge.child_node.word.is_some())
+            .map(|edge| *edge.idx_range.start())
+    }
+    /// Look up the word `W` at the given index
+    ///
+    /// The search descends along the edges whose `idx_range` contains the index.
+    pub fn get_by_index(&self, idx: usize) -> Option<&W> {
+        let mut edges: &[WordTreeEdge<'_, L, W>] = &self.edges;
+        loop {
+            let edge = find_edge_with_idx(edges, idx)?;
+            if *edge.idx_range.start() == idx && edge.child_node.word.is_some() {
+                return edge.child_node.word.as_ref();
+            }
+            edges = &edge.child_node.edges;
+        }
+    }
+    /// Get the range of indices of the words whose labels start with the given labels
+    ///
+    /// The empty prefix matches every word in the tree.
+    pub fn prefix_range<K: Borrow<L>>(
+        &self,
+        prefix: impl IntoIterator<Item = K>,
+    ) -> Option<RangeInclusive<usize>> {
+        self.find_prefix_edge_by(prefix, |label, k: &K| label.cmp(k.borrow()))
+    }
+    /// Returns an iterator over the words `W` whose labels start with the given labels
+    pub fn words_with_prefix<K: Borrow<L>>(
+        &self,
+        prefix: impl IntoIterator<Item = K>,
+    ) -> WordTreeWords<'_, 'a, L, W> {
+        self.words_with_prefix_by(prefix, |label, k: &K| label.cmp(k.borrow()))
+    }
+    /// Find the edge leading to the node for the given sequence of labels
+    fn find_edge<K: Borrow<L>>(
+        &self,
+        labels: impl IntoIterator<Item = K>,
+    ) -> Option<&WordTreeEdge<'_, L, W>> {
+        self.find_edge_by(labels, |label, k| label.cmp(k.borrow()))
+    }
+}
+
+impl<'a, L, W> WordTree<'a, L, W> {
+    /// Find the edge leading to the node for the given sequence of keys,
+    /// following at each node the edge whose label compares equal to the next key
+    ///
+    /// The edges of each node are binary searched, so `compare` must order the labels
+    /// the same way as [`Ord`] does.
+    /// Returns `None` for the empty sequence, which leads to the root node.
+    pub(crate) fn find_edge_by<K>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        compare: impl Fn(&L, &K) -> Ordering,
+    ) -> Option<&WordTreeEdge<'a, L, W>> {
+        let mut edges: &[WordTreeEdge<'a, L, W>] = &self.edges;
+        let mut found = None;
+        for key in keys {
+            let edge = find_edge_with_label(edges, |label| compare(label, &key))?;
+            edges = &edge.child_node.edges;
+            found = Some(edge);
+        }
+        found
+    }
+    /// Get the range of indices of the words under the given sequence of keys,
+    /// where the empty sequence matches every word in the tree
+    pub(crate) fn find_prefix_edge_by<K>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        compare: impl Fn(&L, &K) -> Ordering,
+    ) -> Option<RangeInclusive<usize>> {
+        let mut keys = keys.into_iter().peekable();
+        if keys.peek().is_none() {
+            let len = self
+                .edges
+                .iter()
+                .map(|edge| edge.idx_range.end() + 1 - edge.idx_range.start())
+                .sum::<usize>();
+            return (len > 0).then(|| 0..=len - 1);
+        }
+        self.find_edge_by(keys, compare)
+            .map(|edge| edge.idx_range.clone())
+    }
+    /// Returns an iterator over the words `W` under the given sequence of keys,
+    /// where the empty sequence matches every word in the tree
+    pub(crate) fn words_with_prefix_by<K>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        compare: impl Fn(&L, &K) -> Ordering,
+    ) -> WordTreeWords<'_, 'a, L, W> {
+        let mut keys = keys.into_iter().peekable();
+        if keys.peek().is_none() {
+            return WordTreeWords::new(&self.edges);
+        }
+        match self.find_edge_by(keys, compare) {
+            Some(edge) => WordTreeWords::new(core::slice::from_ref(edge)),
+            None => WordTreeWords::new(&[]),
+        }
+    }
+}
+
+/// Binary search the edges of a node, which are sorted by label,
+/// for the edge whose label compares equal to the key that `compare` compares it to
+fn find_edge_with_label<'e, 'a, L, W>(
+    edges: &'e [WordTreeEdge<'a, L, W>],
+    compare: impl Fn(&L) -> Ordering,
+) -> Option<&'e WordTreeEdge<'a, L, W>> {
+    let i = edges.binary_search_by(|edge| compare(&edge.label)).ok()?;
+    Some(&edges[i])
+}
+
+/// Binary search the edges of a node, whose `idx_range`s are in ascending order,
+/// for the edge whose `idx_range` contains the index
+fn find_edge_with_idx<'e, 'a, L, W>(
+    edges: &'e [WordTreeEdge<'a, L, W>],
+    idx: usize,
+) -> Option<&'e WordTreeEdge<'a, L, W>> {
+    let i = edges
+        .binary_search_by(|edge| {
+            if *edge.idx_range.end() < idx {
+                Ordering::Less
+            } else if *edge.idx_range.start() > idx {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()?;
+    Some(&edges[i])
+}
+
+pub(crate) struct WordTreeEdge<'a, L, W> {
+    pub(crate) label: L,
+    pub(crate) idx_range: RangeInclusive<usize>,
+    pub(crate) child_node: WordTreeNode<'a, L, W>,
+}
+
+impl<L, W> WordTreeEdge<'_, L, W> {
+    const fn get_max_depth(&self, depth_at_parent_node: usize) -> usize {
+        self.child_node.get_max_depth(depth_at_parent_node)
+    }
+    const fn is_fully_well_formed(&self) -> bool {
+        self.child_node.is_fully_well_formed()
+    }
+    const fn is_suitable_for_iterative_search(&self) -> bool {
+        self.child_node.is_suitable_for_iterative_search()
+    }
+}
+
+pub(crate) struct WordTreeNode<'a, L, W> {
+    pub(crate) word: Option<W>,
+    pub(crate) edges: Edges<'a, WordTreeEdge<'a, L, W>>,
+}
+
+/// The child edges of a node
+///
+/// Trees written out as consts borrow their edges, while trees that are built
+/// at runtime (see [`WordTreeBuilder`]) own their edges. Owned edges require the `std` feature.
+pub(crate) enum Edges<'a, E> {
+    Borrowed(&'a [E]),
+    #[cfg(feature = "std")]
+    Owned(Vec<E>),
+}
+
+impl<E> Edges<'_, E> {
+    /// Get the edges as a slice, also in const fns, where [`Deref`] is not available
+    pub(crate) const fn as_slice(&self) -> &[E] {
+        match self {
+            Edges::Borrowed(edges) => edges,
+            #[cfg(feature = "std")]
+            Edges::Owned(edges) => edges.as_slice(),
+        }
+    }
+}
+
+impl<E> Default for Edges<'_, E> {
+    fn default() -> Self {
+        Edges::Borrowed(&[])
+    }
+}
+
+impl<E> Deref for Edges<'_, E> {
+    type Target = [E];
+
+    fn deref(&self) -> &[E] {
+        self.as_slice()
+    }
+}
+
+impl<L, W> WordTreeNode<'_, L, W> {
+    const fn get_max_depth(&self, depth_at_parent_edge: usize) -> usize {
+        let curr_depth = depth_at_parent_edge + 1;
+        let edges = self.edges.as_slice();
+        let mut max_depth = curr_depth;
+        let mut i = 0;
+        while i < edges.len() {
+            let depth = edges[i].get_max_depth(curr_depth);
+            if depth > max_depth {
+                max_depth = depth;
+            }
+            i += 1;
+        }
+        max_depth
+    }
+    const fn is_fully_well_formed(&self) -> bool {
+        let edges = self.edges.as_slice();
+        if edges.is_empty() {
+            return self.word.is_some();
+        }
+        let mut i = 0;
+        while i < edges.len() {
+            if !edges[i].is_fully_well_formed() {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+    pub(crate) fn node_count(&self) -> usize {
+        1 + self
+            .edges
+            .iter()
+            .map(|edge| edge.child_node.node_count())
+            .sum::<usize>()
+    }
+    const fn is_suitable_for_iterative_search(&self) -> bool {
+        let edges = self.edges.as_slice();
+        if edges.is_empty() {
+            return true;
+        }
+        if self.word.is_some() {
+            return false;
+        }
+        let mut i = 0;
+        while i < edges.len() {
+            if !edges[i].is_suitable_for_iterative_search() {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    /// Create a tree with byte labels, where the word of each string is the string itself
+    pub fn example_tree(words: &[&'static str]) -> WordTree<'static, u8, &'static str> {
+        words.iter().map(|&word| (word.bytes(), word)).collect()
+    }
+
+    fn example_byte_tree() -> WordTree<'static, u8, &'static str> {
+        [&b"arm"[..], b"army", b"man", b"\x00\xff"]
+            .into_iter()
+            .map(|bytes| {
+                (
+                    bytes.iter().copied(),
+                    std::str::from_utf8(bytes).unwrap_or("?"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_byte_tree() {
+        let tree = example_byte_tree();
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.get_max_depth(), 4);
+        assert!(tree.is_fully_well_formed());
+        assert!(!tree.is_suitable_for_iterative_search());
+        assert_eq!(
+            tree.words().copied().collect::<Vec<_>>(),
+            ["?", "arm", "army", "man"]
+        );
+        assert_eq!(tree.get(b"army"), Some(&"army"));
+        assert_eq!(tree.get_index(*b"man"), Some(3));
+        assert_eq!(tree.get(b"ar"), None);
+        assert_eq!(tree.prefix_range(b"ar"), Some(1..=2));
+        assert_eq!(tree.prefix_range(b""), Some(0..=3));
+        assert_eq!(tree.words_with_prefix(b"arm").count(), 2);
+        assert_eq!(tree.get_by_index(0), Some(&"?"));
+    }
+
+    #[test_case(&[&[7, 3], &[7, 3, 12]], 3, false ; "nested phonemes")]
+    #[test_case(&[&[7, 3], &[7, 12]], 2, true ; "disjoint phonemes")]
+    fn test_phoneme_tree(words: &[&[u16]], max_depth: usize, iterative: bool) {
+        let tree: WordTree<u16, usize> = words
+            .iter()
+            .enumerate()
+            .map(|(idx, phonemes)| (phonemes.iter().copied(), idx))
+            .collect();
+        assert_eq!(tree.get_max_depth(), max_depth);
+        assert_eq!(tree.is_suitable_for_iterative_search(), iterative);
+        assert_eq!(tree.words().copied().collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn test_phrase_tree() {
+        let tree: WordTree<&str, &str> = [
+            ("army man".split(' '), "army man"),
+            ("arm".split(' '), "arm"),
+            ("man arm".split(' '), "man arm"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(tree.get_max_depth(), 2);
+        assert_eq!(tree.get(["army", "man"]), Some(&"army man"));
+        assert_eq!(tree.get(["army"]), None);
+        assert_eq!(
+            tree.words().copied().collect::<Vec<_>>(),
+            ["arm", "army man", "man arm"]
+        );
+    }
+
+    #[test]
+    fn test_negative_fully_well_formed_leaf_without_word() {
+        let tree: WordTree<u8, ()> = WordTree {
+            edges: Edges::Borrowed(&[WordTreeEdge {
+                label: b'a',
+                idx_range: 0..=0,
+                child_node: WordTreeNode {
+                    word: None,
+                    edges: Edges::Borrowed(&[]),
+                },
+            }]),
+        };
+        assert!(!tree.is_fully_well_formed());
+    }
+}
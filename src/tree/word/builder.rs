@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use super::{Edges, WordTree, WordTreeEdge, WordTreeNode};
+
+/// Builds an owned [`WordTree`] from words inserted in any order
+///
+/// The edges of every node are sorted by label, and the `idx_range`s of the edges are
+/// computed when the tree is built, so that the index of each word is its position
+/// in the order of [`WordTree::words`].
+pub struct WordTreeBuilder<L, W> {
+    root: BuilderNode<L, W>,
+    len: usize,
+}
+
+struct BuilderNode<L, W> {
+    word: Option<W>,
+    children: BTreeMap<L, BuilderNode<L, W>>,
+}
+
+impl<L, W> Default for BuilderNode<L, W> {
+    fn default() -> Self {
+        Self {
+            word: None,
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+impl<L: Ord, W> Default for WordTreeBuilder<L, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: Ord, W> WordTreeBuilder<L, W> {
+    /// Create a builder for an empty tree
+    pub fn new() -> Self {
+        Self {
+            root: BuilderNode::default(),
+            len: 0,
+        }
+    }
+    /// Insert a word `W` under the given sequence of labels
+    ///
+    /// Returns the word that was previously inserted under the same labels, if any.
+    /// An empty sequence of labels cannot be represented in the tree and is ignored,
+    /// in which case the word is handed back.
+    pub fn insert(&mut self, labels: impl IntoIterator<Item = L>, word: W) -> Option<W> {
+        let mut labels = labels.into_iter().peekable();
+        if labels.peek().is_none() {
+            return Some(word);
+        }
+        let mut node = &mut self.root;
+        for label in labels {
+            node = node.children.entry(label).or_default();
+        }
+        let prev = node.word.replace(word);
+        if prev.is_none() {
+            self.len += 1;
+        }
+        prev
+    }
+    /// Get the number of words inserted so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if no words have been inserted yet
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Build the tree
+    pub fn build<'a>(self) -> WordTree<'a, L, W> {
+        let mut next_idx = 0;
+        WordTree {
+            edges: build_edges(self.root.children, &mut next_idx),
+        }
+    }
+}
+
+fn build_edges<'a, L, W>(
+    children: BTreeMap<L, BuilderNode<L, W>>,
+    next_idx: &mut usize,
+) -> Edges<'a, WordTreeEdge<'a, L, W>> {
+    if children.is_empty() {
+        return Edges::default();
+    }
+    let edges = children
+        .into_iter()
+        .map(|(label, child)| {
+            let start = *next_idx;
+            if child.word.is_some() {
+                *next_idx += 1;
+            }
+            let edges = build_edges(child.children, next_idx);
+            WordTreeEdge {
+                label,
+                idx_range: start..=*next_idx - 1,
+                child_node: WordTreeNode {
+                    word: child.word,
+                    edges,
+                },
+            }
+        })
+        .collect();
+    Edges::Owned(edges)
+}
+
+impl<I: IntoIterator<Item = L>, L: Ord, W> FromIterator<(I, W)> for WordTree<'_, L, W> {
+    fn from_iter<T: IntoIterator<Item = (I, W)>>(iter: T) -> Self {
+        let mut builder = WordTreeBuilder::new();
+        for (labels, word) in iter {
+            builder.insert(labels, word);
+        }
+        builder.build()
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use super::{WordTree, WordTreeEdge};
+
+impl<'a, L, W> WordTree<'a, L, W> {
+    /// Returns an iterator over the words `W` of the tree, shortest first
+    ///
+    /// The length of a word is its number of labels. Words of the same length
+    /// are visited in the order of [`Self::words`].
+    pub fn words_bfs(&self) -> WordTreeWordsByLen<'_, 'a, L, W> {
+        WordTreeWordsByLen(WordsByLenInner::Bfs(self.edges.iter().collect()))
+    }
+    /// Returns an iterator over the words `W` of the tree that have exactly `len` labels,
+    /// in the order of [`Self::words`]
+    pub fn words_of_len(&self, len: usize) -> WordTreeWordsByLen<'_, 'a, L, W> {
+        let mut stack = vec![];
+        if len > 0 {
+            stack.push((&self.edges[..], 0));
+        }
+        WordTreeWordsByLen(WordsByLenInner::OfLen { len, stack })
+    }
+}
+
+/// An iterator over the words `W` of a [`WordTree`] by length, returned by
+/// [`WordTree::words_bfs`] and [`WordTree::words_of_len`]
+///
+/// For all words, shortest first, the edges are visited breadth-first, with a queue of
+/// the edges that are left to visit. Because the edges of every node are in order,
+/// the words of each length come out in order as well.
+///
+/// For the words of one length, the edges are visited depth-first, down to that depth
+/// and no further, so only the nodes up to that depth are visited.
+pub struct WordTreeWordsByLen<'t, 'a, L, W>(WordsByLenInner<'t, 'a, L, W>);
+
+enum WordsByLenInner<'t, 'a, L, W> {
+    /// The edges that are left to visit, in breadth-first order
+    Bfs(VecDeque<&'t WordTreeEdge<'a, L, W>>),
+    /// The edges at each depth down to `len`, and the index of the next edge to visit
+    OfLen {
+        len: usize,
+        stack: Vec<(&'t [WordTreeEdge<'a, L, W>], usize)>,
+    },
+}
+
+impl<'t, L, W> Iterator for WordTreeWordsByLen<'t, '_, L, W> {
+    type Item = &'t W;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            WordsByLenInner::Bfs(queue) => loop {
+                let edge = queue.pop_front()?;
+                queue.extend(edge.child_node.edges.iter());
+                if let Some(word) = &edge.child_node.word {
+                    return Some(word);
+                }
+            },
+            WordsByLenInner::OfLen { len, stack } => loop {
+                let depth = stack.len();
+                let (edges, next_edge) = stack.last_mut()?;
+                let Some(edge) = edges.get(*next_edge) else {
+                    stack.pop();
+                    continue;
+                };
+                *next_edge += 1;
+                if depth < *len {
+                    if !edge.child_node.edges.is_empty() {
+                        stack.push((&edge.child_node.edges, 0));
+                    }
+                } else if let Some(word) = &edge.child_node.word {
+                    return Some(word);
+                }
+            },
+        }
+    }
+}
+
+impl<L, W> FusedIterator for WordTreeWordsByLen<'_, '_, L, W> {}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::example_tree;
+    use super::WordsByLenInner;
+    use test_case::test_case;
+
+    const WORDS: &[&str] = &["a", "abc", "abd", "b", "ba", "bcde", "cd"];
+
+    #[test]
+    fn test_words_bfs() {
+        let tree = example_tree(WORDS);
+        assert_eq!(
+            tree.words_bfs().copied().collect::<Vec<_>>(),
+            ["a", "b", "ba", "cd", "abc", "abd", "bcde"]
+        );
+    }
+
+    #[test_case(0, &[])]
+    #[test_case(1, &["a", "b"])]
+    #[test_case(2, &["ba", "cd"])]
+    #[test_case(3, &["abc", "abd"])]
+    #[test_case(4, &["bcde"])]
+    #[test_case(5, &[])]
+    fn test_words_of_len(len: usize, expected: &[&str]) {
+        let tree = example_tree(WORDS);
+        assert_eq!(
+            tree.words_of_len(len).copied().collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_words_of_len_stops_at_len() {
+        let tree = example_tree(WORDS);
+        let mut words = tree.words_of_len(2);
+        while words.next().is_some() {
+            let WordsByLenInner::OfLen { stack, .. } = &words.0 else {
+                unreachable!();
+            };
+            assert!(stack.len() <= 2);
+        }
+    }
+
+    #[test]
+    fn test_words_bfs_empty() {
+        let tree = example_tree(&[]);
+        assert_eq!(tree.words_bfs().next(), None);
+    }
+}
@@ -0,0 +1,236 @@
+use core::borrow::Borrow;
+use core::ops::RangeInclusive;
+
+use super::{find_edge_with_label, WordTree, WordTreeEdge};
+
+impl<'a, L, W> WordTree<'a, L, W> {
+    /// Get a read-only reference to the root node of the tree
+    pub fn root(&self) -> NodeRef<'_, 'a, L, W> {
+        NodeRef {
+            word: None,
+            edges: &self.edges,
+            depth: 0,
+        }
+    }
+    /// Get a cursor at the root node of the tree
+    #[cfg(feature = "std")]
+    pub fn cursor(&self) -> Cursor<'_, 'a, L, W> {
+        Cursor {
+            root_edges: &self.edges,
+            path: vec![],
+        }
+    }
+}
+
+/// A read-only reference to a node of a [`WordTree`]
+///
+/// Node references can be used to write custom traversals of a tree.
+/// They are cheap to copy, and do not borrow each other, only the tree.
+pub struct NodeRef<'t, 'a, L, W> {
+    word: Option<&'t W>,
+    edges: &'t [WordTreeEdge<'a, L, W>],
+    depth: usize,
+}
+
+impl<L, W> Clone for NodeRef<'_, '_, L, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L, W> Copy for NodeRef<'_, '_, L, W> {}
+
+impl<'t, 'a, L, W> NodeRef<'t, 'a, L, W> {
+    /// Get the word `W` of the node, if any
+    pub fn word(&self) -> Option<&'t W> {
+        self.word
+    }
+    /// Returns an iterator over the child edges of the node, in order
+    ///
+    /// Each edge is given as its label, the range of indices of the words below it,
+    /// and the node that it leads to.
+    pub fn children(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (&'t L, RangeInclusive<usize>, NodeRef<'t, 'a, L, W>)>
+           + ExactSizeIterator
+           + 't {
+        let depth = self.depth + 1;
+        self.edges.iter().map(move |edge| {
+            (
+                &edge.label,
+                edge.idx_range.clone(),
+                NodeRef::child_of(edge, depth),
+            )
+        })
+    }
+    /// Get the node at the end of the child edge with the given label
+    pub fn child<K: Borrow<L>>(&self, label: K) -> Option<NodeRef<'t, 'a, L, W>>
+    where
+        L: Ord,
+    {
+        find_edge_with_label(self.edges, |edge_label| edge_label.cmp(label.borrow()))
+            .map(|edge| NodeRef::child_of(edge, self.depth + 1))
+    }
+    /// Get the number of edges from the root node to this node
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+    /// Returns `true` if the node has no child edges
+    pub fn is_leaf(&self) -> bool {
+        self.edges.is_empty()
+    }
+    fn child_of(edge: &'t WordTreeEdge<'a, L, W>, depth: usize) -> Self {
+        Self {
+            word: edge.child_node.word.as_ref(),
+            edges: &edge.child_node.edges,
+            depth,
+        }
+    }
+}
+
+/// A read-only cursor that moves up and down the nodes of a [`WordTree`]
+///
+/// Unlike a [`NodeRef`], the cursor keeps track of the edges from the root node
+/// to its current node, so that it can report the [path](`Self::path`) to the node
+/// and move back up towards the root node.
+#[cfg(feature = "std")]
+pub struct Cursor<'t, 'a, L, W> {
+    root_edges: &'t [WordTreeEdge<'a, L, W>],
+    path: Vec<&'t WordTreeEdge<'a, L, W>>,
+}
+
+#[cfg(feature = "std")]
+impl<L, W> Clone for Cursor<'_, '_, L, W> {
+    fn clone(&self) -> Self {
+        Self {
+            root_edges: self.root_edges,
+            path: self.path.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'t, 'a, L, W> Cursor<'t, 'a, L, W> {
+    /// Get a reference to the current node
+    pub fn node(&self) -> NodeRef<'t, 'a, L, W> {
+        match self.path.last() {
+            Some(edge) => NodeRef::child_of(edge, self.path.len()),
+            None => NodeRef {
+                word: None,
+                edges: self.root_edges,
+                depth: 0,
+            },
+        }
+    }
+    /// Get the word `W` of the current node, if any
+    pub fn word(&self) -> Option<&'t W> {
+        self.node().word()
+    }
+    /// Returns an iterator over the child edges of the current node,
+    /// same as [`NodeRef::children`]
+    pub fn children(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (&'t L, RangeInclusive<usize>, NodeRef<'t, 'a, L, W>)>
+           + ExactSizeIterator
+           + 't {
+        self.node().children()
+    }
+    /// Get the node at the end of the child edge of the current node with the given label
+    pub fn child<K: Borrow<L>>(&self, label: K) -> Option<NodeRef<'t, 'a, L, W>>
+    where
+        L: Ord,
+    {
+        self.node().child(label)
+    }
+    /// Get the number of edges from the root node to the current node
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+    /// Returns an iterator over the labels of the edges from the root node to the current node
+    pub fn path(&self) -> impl DoubleEndedIterator<Item = &'t L> + ExactSizeIterator + '_ {
+        self.path.iter().map(|edge| &edge.label)
+    }
+    /// Get the range of indices of the words below the edge to the current node,
+    /// or `None` at the root node
+    pub fn idx_range(&self) -> Option<RangeInclusive<usize>> {
+        self.path.last().map(|edge| edge.idx_range.clone())
+    }
+    /// Move the cursor down along the child edge with the given label
+    ///
+    /// Returns `false`, and leaves the cursor where it is, if there is no such edge.
+    pub fn descend<K: Borrow<L>>(&mut self, label: K) -> bool
+    where
+        L: Ord,
+    {
+        let edges = match self.path.last() {
+            Some(edge) => &edge.child_node.edges[..],
+            None => self.root_edges,
+        };
+        match find_edge_with_label(edges, |edge_label| edge_label.cmp(label.borrow())) {
+            Some(edge) => {
+                self.path.push(edge);
+                true
+            }
+            None => false,
+        }
+    }
+    /// Move the cursor up to the parent of the current node
+    ///
+    /// Returns `false` if the cursor is already at the root node.
+    pub fn ascend(&mut self) -> bool {
+        self.path.pop().is_some()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::super::test::example_tree;
+    use super::*;
+
+    /// Count the words below a node with a custom traversal
+    fn count_words(node: NodeRef<'_, '_, u8, &str>) -> usize {
+        usize::from(node.word().is_some())
+            + node
+                .children()
+                .map(|(_, _, child)| count_words(child))
+                .sum::<usize>()
+    }
+
+    #[test]
+    fn test_node_ref() {
+        let tree = example_tree(&["ab", "abc", "b"]);
+        let root = tree.root();
+        assert_eq!(root.word(), None);
+        assert_eq!(root.depth(), 0);
+        assert_eq!(count_words(root), 3);
+        let children = root
+            .children()
+            .map(|(label, idx_range, child)| (*label, idx_range, child.depth()))
+            .collect::<Vec<_>>();
+        assert_eq!(children, [(b'a', 0..=1, 1), (b'b', 2..=2, 1)]);
+        let ab = root.child(b'a').and_then(|a| a.child(b'b')).unwrap();
+        assert_eq!(ab.word(), Some(&"ab"));
+        assert_eq!(ab.depth(), 2);
+        assert!(!ab.is_leaf());
+        assert!(root.child(b'c').is_none());
+    }
+
+    #[test]
+    fn test_cursor() {
+        let tree = example_tree(&["ab", "abc", "b"]);
+        let mut cursor = tree.cursor();
+        assert!(cursor.descend(b'a'));
+        assert!(cursor.descend(b'b'));
+        assert!(!cursor.descend(b'x'));
+        assert_eq!(cursor.word(), Some(&"ab"));
+        assert_eq!(cursor.depth(), 2);
+        assert_eq!(cursor.path().copied().collect::<Vec<_>>(), b"ab");
+        assert_eq!(cursor.idx_range(), Some(0..=1));
+        assert_eq!(cursor.child(b'c').and_then(|c| c.word()), Some(&"abc"));
+        assert!(cursor.ascend());
+        assert!(cursor.ascend());
+        assert!(!cursor.ascend());
+        assert_eq!(cursor.idx_range(), None);
+        assert_eq!(cursor.children().len(), 2);
+    }
+}
@@ -0,0 +1,193 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use super::find_edge_with_idx;
+use super::WordTreeEdge;
+
+/// An iterator over the words `W` of a [`WordTree`](`super::WordTree`),
+/// returned by [`WordTree::words`](`super::WordTree::words`)
+///
+/// The words are visited in order from the front, and in reverse order from the back,
+/// each with an explicit stack of the edges that lead to the current node. A stack is
+/// created the first time that a word is taken from its end, and grows with the depth
+/// of the nodes, so that it is reallocated at most a few times, and not for every node.
+///
+/// The number of words left is known from the `idx_range`s of the edges.
+///
+/// Without the `std` feature, there are no stacks, and each word is instead looked up
+/// by its index, by descending along the edges whose `idx_range` contains the index.
+pub struct WordTreeWords<'t, 'a, L, W> {
+    edges: &'t [WordTreeEdge<'a, L, W>],
+    /// The indices of the words that are left
+    range: Range<usize>,
+    /// The edges at each depth, and the index of the next edge to visit
+    #[cfg(feature = "std")]
+    front: Option<Vec<FrontFrame<'t, 'a, L, W>>>,
+    /// The edges at each depth, the number of edges that are left to visit,
+    /// and the word of the node that the edges start from, which is visited after them
+    #[cfg(feature = "std")]
+    back: Option<Vec<BackFrame<'t, 'a, L, W>>>,
+}
+
+#[cfg(feature = "std")]
+type FrontFrame<'t, 'a, L, W> = (&'t [WordTreeEdge<'a, L, W>], usize);
+#[cfg(feature = "std")]
+type BackFrame<'t, 'a, L, W> = (&'t [WordTreeEdge<'a, L, W>], usize, Option<&'t W>);
+
+impl<'t, 'a, L, W> WordTreeWords<'t, 'a, L, W> {
+    /// Iterate over the words under the given edges
+    pub(crate) fn new(edges: &'t [WordTreeEdge<'a, L, W>]) -> Self {
+        let start = edges.first().map_or(0, |edge| *edge.idx_range.start());
+        let len = edges
+            .iter()
+            .map(|edge| edge.idx_range.end() + 1 - edge.idx_range.start())
+            .sum::<usize>();
+        Self {
+            edges,
+            range: start..start + len,
+            #[cfg(feature = "std")]
+            front: None,
+            #[cfg(feature = "std")]
+            back: None,
+        }
+    }
+    #[cfg(feature = "std")]
+    fn next_from_front(&mut self) -> Option<&'t W> {
+        let edges = self.edges;
+        let stack = self.front.get_or_insert_with(|| vec![(edges, 0)]);
+        loop {
+            let (edges, next_edge) = stack.last_mut()?;
+            let Some(edge) = edges.get(*next_edge) else {
+                stack.pop();
+                continue;
+            };
+            *next_edge += 1;
+            // Visit the node itself before the nodes below it.
+            if !edge.child_node.edges.is_empty() {
+                stack.push((&edge.child_node.edges, 0));
+            }
+            if let Some(word) = &edge.child_node.word {
+                return Some(word);
+            }
+        }
+    }
+    #[cfg(feature = "std")]
+    fn next_from_back(&mut self) -> Option<&'t W> {
+        let edges = self.edges;
+        let stack = self
+            .back
+            .get_or_insert_with(|| vec![(edges, edges.len(), None)]);
+        loop {
+            let (edges, edges_left, word) = stack.last_mut()?;
+            if *edges_left == 0 {
+                // Visit the node itself after the nodes below it.
+                let word = *word;
+                stack.pop();
+                if word.is_some() {
+                    return word;
+                }
+                continue;
+            }
+            *edges_left -= 1;
+            let child_node = &edges[*edges_left].child_node;
+            stack.push((
+                &child_node.edges,
+                child_node.edges.len(),
+                child_node.word.as_ref(),
+            ));
+        }
+    }
+    #[cfg(not(feature = "std"))]
+    fn next_from_front(&mut self) -> Option<&'t W> {
+        self.word_at(self.range.start)
+    }
+    #[cfg(not(feature = "std"))]
+    fn next_from_back(&mut self) -> Option<&'t W> {
+        self.word_at(self.range.end - 1)
+    }
+    #[cfg(not(feature = "std"))]
+    fn word_at(&self, idx: usize) -> Option<&'t W> {
+        let mut edges = self.edges;
+        loop {
+            let edge = find_edge_with_idx(edges, idx)?;
+            if *edge.idx_range.start() == idx && edge.child_node.word.is_some() {
+                return edge.child_node.word.as_ref();
+            }
+            edges = edge.child_node.edges.as_slice();
+        }
+    }
+}
+
+impl<'t, L, W> Iterator for WordTreeWords<'t, '_, L, W> {
+    type Item = &'t W;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let word = self.next_from_front()?;
+        self.range.start += 1;
+        Some(word)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.range.len(), Some(self.range.len()))
+    }
+}
+
+impl<L, W> DoubleEndedIterator for WordTreeWords<'_, '_, L, W> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let word = self.next_from_back()?;
+        self.range.end -= 1;
+        Some(word)
+    }
+}
+
+impl<L, W> ExactSizeIterator for WordTreeWords<'_, '_, L, W> {}
+
+impl<L, W> FusedIterator for WordTreeWords<'_, '_, L, W> {}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::super::test::example_tree;
+
+    #[test]
+    fn test_stacks_are_bounded_by_depth() {
+        // One level for the root edges, and one for each edge down to the deepest node
+        let tree = example_tree(&["a", "ab", "abc", "abcd", "b"]);
+        let mut words = tree.words();
+        assert!(words.front.is_none());
+        while words.next().is_some() {
+            assert!(words.front.as_ref().unwrap().len() <= 5);
+        }
+        let mut words = tree.words();
+        assert!(words.back.is_none());
+        while words.next_back().is_some() {
+            assert!(words.back.as_ref().unwrap().len() <= 5);
+        }
+    }
+
+    #[test]
+    fn test_words_from_both_ends_meet() {
+        let tree = example_tree(&["a", "ab", "abc", "b", "ba"]);
+        let mut words = tree.words();
+        assert_eq!(words.next_back(), Some(&"ba"));
+        assert_eq!(words.next(), Some(&"a"));
+        assert_eq!(words.next_back(), Some(&"b"));
+        assert_eq!(words.len(), 2);
+        assert_eq!(words.next(), Some(&"ab"));
+        assert_eq!(words.next_back(), Some(&"abc"));
+        assert_eq!(words.next(), None);
+        assert_eq!(words.next_back(), None);
+    }
+
+    #[test]
+    fn test_words_with_prefix_stays_in_subtree() {
+        let tree = example_tree(&["a", "ab", "abc", "b"]);
+        let words = tree.words_with_prefix(b"ab");
+        assert_eq!(words.rev().collect::<Vec<_>>(), [&"abc", &"ab"]);
+    }
+}
@@ -0,0 +1,113 @@
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::iter::ParallelIterator;
+
+use super::{WordTree, WordTreeEdge, WordTreeWords};
+
+impl<'a, L: Sync, W: Sync> WordTree<'a, L, W> {
+    /// Returns a parallel iterator over the words `W` of the tree
+    ///
+    /// The work is split between the edges of the root node by the sizes of their `idx_range`s,
+    /// so that each half has about as many words, and edges with more words than that
+    /// are split between their own child edges.
+    /// Collecting the words keeps them in the order of [`Self::words`].
+    pub fn par_words(&self) -> ParWords<'_, 'a, L, W> {
+        ParWords {
+            producer: WordsProducer {
+                word: None,
+                edges: &self.edges,
+            },
+        }
+    }
+}
+
+/// A parallel iterator over the words `W` of a [`WordTree`], returned by [`WordTree::par_words`]
+pub struct ParWords<'t, 'a, L, W> {
+    producer: WordsProducer<'t, 'a, L, W>,
+}
+
+impl<'t, L: Sync, W: Sync> ParallelIterator for ParWords<'t, '_, L, W> {
+    type Item = &'t W;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge_unindexed(self.producer, consumer)
+    }
+}
+
+/// The word of a node, if any, followed by the words under some of its edges
+struct WordsProducer<'t, 'a, L, W> {
+    word: Option<&'t W>,
+    edges: &'t [WordTreeEdge<'a, L, W>],
+}
+
+fn edge_len<L, W>(edge: &WordTreeEdge<'_, L, W>) -> usize {
+    edge.idx_range.end() + 1 - edge.idx_range.start()
+}
+
+impl<'t, L: Sync, W: Sync> UnindexedProducer for WordsProducer<'t, '_, L, W> {
+    type Item = &'t W;
+
+    fn split(mut self) -> (Self, Option<Self>) {
+        // Descend past nodes that have a single edge and no word of their own.
+        while let ([edge], None) = (self.edges, self.word) {
+            self.word = edge.child_node.word.as_ref();
+            self.edges = &edge.child_node.edges;
+        }
+        let edges_len = self.edges.iter().map(edge_len).sum::<usize>();
+        if edges_len + usize::from(self.word.is_some()) < 2 {
+            return (self, None);
+        }
+        // Split after the edge where the words on the left reach half of the words,
+        // leaving at least one edge on each side when there is more than one edge.
+        let mid = if self.edges.len() == 1 {
+            0
+        } else {
+            let mut left_len = 0;
+            self.edges
+                .iter()
+                .position(|edge| {
+                    left_len += edge_len(edge);
+                    left_len * 2 >= edges_len
+                })
+                .map_or(1, |idx| idx + 1)
+                .min(self.edges.len() - 1)
+        };
+        let (left, right) = self.edges.split_at(mid);
+        self.edges = left;
+        (
+            self,
+            Some(WordsProducer {
+                word: None,
+                edges: right,
+            }),
+        )
+    }
+    fn fold_with<F: Folder<Self::Item>>(self, folder: F) -> F {
+        folder
+            .consume_iter(self.word)
+            .consume_iter(WordTreeWords::new(self.edges))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn producer_len<L, W>(producer: &WordsProducer<'_, '_, L, W>) -> usize {
+        usize::from(producer.word.is_some()) + producer.edges.iter().map(edge_len).sum::<usize>()
+    }
+
+    #[test]
+    fn test_split_by_idx_range_sizes() {
+        let words: [&[u8]; 6] = [b"aa", b"ab", b"ac", b"ad", b"b", b"c"];
+        let tree = words
+            .iter()
+            .map(|word| (word.iter().copied(), ()))
+            .collect::<WordTree<u8, ()>>();
+        let (left, right) = tree.par_words().producer.split();
+        let right = right.unwrap();
+        assert_eq!((producer_len(&left), producer_len(&right)), (4, 2));
+        // A single edge is split between its own child edges.
+        let (left, right) = left.split();
+        assert_eq!((producer_len(&left), producer_len(&right.unwrap())), (2, 2));
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+use super::{Edges, WordTree, WordTreeEdge, WordTreeNode};
+
+/// Which words of two trees are kept by a set operation
+#[derive(Clone, Copy)]
+enum SetOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl<L: Ord + Clone, W: Clone> WordTree<'_, L, W> {
+    /// Create a new tree with the words of both trees
+    ///
+    /// Words that are in both trees are combined with `resolve`, which is given
+    /// the word of this tree and then the word of the other tree.
+    ///
+    /// The trees are merged by walking the sorted edges of both trees side by side,
+    /// and the `idx_range`s of the new tree are computed as it is built.
+    pub fn union<'b>(
+        &self,
+        other: &WordTree<'_, L, W>,
+        resolve: impl Fn(&W, &W) -> W,
+    ) -> WordTree<'b, L, W> {
+        self.merge(other, SetOp::Union, &resolve)
+    }
+    /// Create a new tree with the words that are in both trees
+    ///
+    /// The words of the two trees are combined with `resolve`, which is given
+    /// the word of this tree and then the word of the other tree.
+    pub fn intersection<'b>(
+        &self,
+        other: &WordTree<'_, L, W>,
+        resolve: impl Fn(&W, &W) -> W,
+    ) -> WordTree<'b, L, W> {
+        self.merge(other, SetOp::Intersection, &resolve)
+    }
+    /// Create a new tree with the words of this tree that are not in the other tree
+    pub fn difference<'b>(&self, other: &WordTree<'_, L, W>) -> WordTree<'b, L, W> {
+        self.merge(other, SetOp::Difference, &|a, _| a.clone())
+    }
+    fn merge<'b>(
+        &self,
+        other: &WordTree<'_, L, W>,
+        op: SetOp,
+        resolve: &dyn Fn(&W, &W) -> W,
+    ) -> WordTree<'b, L, W> {
+        let mut next_idx = 0;
+        WordTree {
+            edges: merge_edges(&self.edges, &other.edges, op, resolve, &mut next_idx),
+        }
+    }
+}
+
+/// Merge two sorted slices of edges, dropping the edges whose subtrees end up without words
+fn merge_edges<'b, L: Ord + Clone, W: Clone>(
+    a: &[WordTreeEdge<'_, L, W>],
+    b: &[WordTreeEdge<'_, L, W>],
+    op: SetOp,
+    resolve: &dyn Fn(&W, &W) -> W,
+    next_idx: &mut usize,
+) -> Edges<'b, WordTreeEdge<'b, L, W>> {
+    let mut edges = vec![];
+    let mut a = a.iter().peekable();
+    let mut b = b.iter().peekable();
+    loop {
+        let (label, a_node, b_node) = match (a.peek(), b.peek()) {
+            (None, None) => break,
+            (Some(a_edge), Some(b_edge)) => match a_edge.label.cmp(&b_edge.label) {
+                Ordering::Less => (&a_edge.label, a.next().map(|e| &e.child_node), None),
+                Ordering::Greater => (&b_edge.label, None, b.next().map(|e| &e.child_node)),
+                Ordering::Equal => (
+                    &a_edge.label,
+                    a.next().map(|e| &e.child_node),
+                    b.next().map(|e| &e.child_node),
+                ),
+            },
+            (Some(a_edge), None) => (&a_edge.label, a.next().map(|e| &e.child_node), None),
+            (None, Some(b_edge)) => (&b_edge.label, None, b.next().map(|e| &e.child_node)),
+        };
+        let keep = match op {
+            SetOp::Union => true,
+            SetOp::Intersection => a_node.is_some() && b_node.is_some(),
+            SetOp::Difference => a_node.is_some(),
+        };
+        if !keep {
+            continue;
+        }
+        let a_word = a_node.and_then(|node| node.word.as_ref());
+        let b_word = b_node.and_then(|node| node.word.as_ref());
+        let word = match (op, a_word, b_word) {
+            (SetOp::Union | SetOp::Intersection, Some(a), Some(b)) => Some(resolve(a, b)),
+            (SetOp::Union | SetOp::Difference, Some(a), None) => Some(a.clone()),
+            (SetOp::Union, None, Some(b)) => Some(b.clone()),
+            _ => None,
+        };
+        let start = *next_idx;
+        if word.is_some() {
+            *next_idx += 1;
+        }
+        let child_edges = merge_edges(
+            a_node.map_or(&[][..], |node| &node.edges[..]),
+            b_node.map_or(&[][..], |node| &node.edges[..]),
+            op,
+            resolve,
+            next_idx,
+        );
+        if *next_idx == start {
+            continue;
+        }
+        edges.push(WordTreeEdge {
+            label: label.clone(),
+            idx_range: start..=*next_idx - 1,
+            child_node: WordTreeNode {
+                word,
+                edges: child_edges,
+            },
+        });
+    }
+    if edges.is_empty() {
+        Edges::default()
+    } else {
+        Edges::Owned(edges)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::example_tree;
+
+    #[test]
+    fn test_union_resolves_common_words() {
+        let a = example_tree(&["ab", "abc"]).map(|word| word.to_string());
+        let b = example_tree(&["ab", "b"]).map(|word| word.to_uppercase());
+        let union = a.union(&b, |a, b| format!("{a}{b}"));
+        assert_eq!(union.words().collect::<Vec<_>>(), ["abAB", "abc", "B"]);
+        assert_eq!(union.get(b"ab").map(String::as_str), Some("abAB"));
+        assert_eq!(union.get_index(b"b"), Some(2));
+    }
+
+    #[test]
+    fn test_difference_prunes_empty_subtrees() {
+        let a = example_tree(&["abc", "b"]);
+        let b = example_tree(&["abc"]);
+        let difference = a.difference(&b);
+        assert_eq!(difference.words().copied().collect::<Vec<_>>(), ["b"]);
+        assert_eq!(difference.node_count(), 2);
+        assert!(difference.is_fully_well_formed());
+    }
+}
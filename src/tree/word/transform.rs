@@ -0,0 +1,112 @@
+use super::{Edges, WordTree, WordTreeEdge, WordTreeNode};
+
+impl<L: Clone, W> WordTree<'_, L, W> {
+    /// Create a new tree with the words for which `pred` returns `true`
+    ///
+    /// The predicate is given the sequence of labels of each word along with the word.
+    /// Subtrees that end up without words are dropped, so that the new tree is
+    /// [fully well-formed](`Self::is_fully_well_formed`), and the `idx_range`s
+    /// of the new tree are computed as it is built.
+    pub fn filter<'b>(&self, pred: impl Fn(&[L], &W) -> bool) -> WordTree<'b, L, W>
+    where
+        W: Clone,
+    {
+        let mut next_idx = 0;
+        WordTree {
+            edges: filter_edges(&self.edges, &mut vec![], &pred, &mut next_idx),
+        }
+    }
+    /// Create a new tree with the same labels, where each word is mapped with `f`
+    ///
+    /// The shape of the tree, and so the index of every word, is unchanged.
+    pub fn map<'b, V>(&self, f: impl Fn(&W) -> V) -> WordTree<'b, L, V> {
+        WordTree {
+            edges: map_edges(&self.edges, &f),
+        }
+    }
+}
+
+fn filter_edges<'b, L: Clone, W: Clone>(
+    edges: &[WordTreeEdge<'_, L, W>],
+    path: &mut Vec<L>,
+    pred: &dyn Fn(&[L], &W) -> bool,
+    next_idx: &mut usize,
+) -> Edges<'b, WordTreeEdge<'b, L, W>> {
+    let mut filtered = vec![];
+    for edge in edges {
+        path.push(edge.label.clone());
+        let word = edge
+            .child_node
+            .word
+            .as_ref()
+            .filter(|word| pred(path, word))
+            .cloned();
+        let start = *next_idx;
+        if word.is_some() {
+            *next_idx += 1;
+        }
+        let child_edges = filter_edges(&edge.child_node.edges, path, pred, next_idx);
+        path.pop();
+        if *next_idx == start {
+            continue;
+        }
+        filtered.push(WordTreeEdge {
+            label: edge.label.clone(),
+            idx_range: start..=*next_idx - 1,
+            child_node: WordTreeNode {
+                word,
+                edges: child_edges,
+            },
+        });
+    }
+    if filtered.is_empty() {
+        Edges::default()
+    } else {
+        Edges::Owned(filtered)
+    }
+}
+
+fn map_edges<'b, L: Clone, W, V>(
+    edges: &[WordTreeEdge<'_, L, W>],
+    f: &dyn Fn(&W) -> V,
+) -> Edges<'b, WordTreeEdge<'b, L, V>> {
+    if edges.is_empty() {
+        return Edges::default();
+    }
+    Edges::Owned(
+        edges
+            .iter()
+            .map(|edge| WordTreeEdge {
+                label: edge.label.clone(),
+                idx_range: edge.idx_range.clone(),
+                child_node: WordTreeNode {
+                    word: edge.child_node.word.as_ref().map(f),
+                    edges: map_edges(&edge.child_node.edges, f),
+                },
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::example_tree;
+
+    #[test]
+    fn test_filter_by_labels() {
+        let tree = example_tree(&["ab", "abc", "b", "ba"]);
+        let filtered = tree.filter(|labels, _| labels.first() == Some(&b'a'));
+        assert_eq!(filtered.words().copied().collect::<Vec<_>>(), ["ab", "abc"]);
+        assert_eq!(filtered.node_count(), 4);
+        assert!(filtered.is_fully_well_formed());
+    }
+
+    #[test]
+    fn test_map_keeps_indices() {
+        let tree = example_tree(&["ab", "abc", "b"]);
+        let mapped = tree.map(|word| word.len() * 10);
+        assert_eq!(mapped.words().copied().collect::<Vec<_>>(), [20, 30, 10]);
+        assert_eq!(mapped.get_index(b"abc"), tree.get_index(b"abc"));
+        assert_eq!(mapped.node_count(), tree.node_count());
+    }
+}
@@ -1,145 +1,103 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
+use core::str::Chars;
 
-use crate::Words;
+#[cfg(feature = "std")]
+use super::word::{Cursor, WordTreeBuilder, WordTreeWordsByLen};
+#[cfg(any(feature = "std", test))]
+use super::word::{Edges, WordTreeEdge, WordTreeNode};
+use super::word::{NodeRef, WordTree, WordTreeWords};
+use normalization::edge_chars;
 
-/// An iterator over the words of a [`WordCharTreeRootNode`]
-struct Iter<'a, W> {
-    root: &'a WordCharTreeRootNode<'a, W>,
-    curr_edge: usize,
-    curr_node: Option<&'a WordCharTreeNode<'a, W>>,
-    curr_node_visitor: Option<WordCharTreeNodeVisitor<'a, W>>,
-}
+#[cfg(feature = "std")]
+mod binary;
+#[cfg(feature = "std")]
+mod builder;
+mod case_folding;
+#[cfg(feature = "std")]
+mod dawg;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "graphemes")]
+mod grapheme;
+mod normalization;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "std")]
+mod prefix_free;
+#[cfg(feature = "std")]
+mod radix;
+#[cfg(feature = "std")]
+mod render;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "std")]
+mod set_ops;
+#[cfg(feature = "std")]
+mod transform;
+#[cfg(feature = "std")]
+mod view;
 
-impl<'a, W> Iter<'a, W> {
-    fn boxed(root: &'a WordCharTreeRootNode<'a, W>) -> Box<Self> {
-        let (curr_node, curr_node_visitor) = if root.edges.is_empty() {
-            (None, None)
-        } else {
-            (
-                Some(&root.edges[0].child_node),
-                Some(WordCharTreeNodeVisitor::new(&root.edges[0].child_node)),
-            )
-        };
-        Box::new(Self {
-            root,
-            curr_edge: 0,
-            curr_node,
-            curr_node_visitor,
-        })
-    }
-}
+#[cfg(feature = "std")]
+pub use binary::*;
+#[cfg(feature = "std")]
+pub use builder::*;
+pub use case_folding::*;
+#[cfg(feature = "std")]
+pub use dawg::*;
+#[cfg(feature = "std")]
+pub use diff::*;
+#[cfg(feature = "graphemes")]
+pub use grapheme::*;
+pub use normalization::*;
+#[cfg(feature = "std")]
+pub use prefix_free::*;
+#[cfg(feature = "std")]
+pub use radix::*;
+#[cfg(feature = "std")]
+pub use view::*;
 
-impl<'a, W> Iterator for Iter<'a, W> {
-    type Item = &'a W;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let Some(visitor) = &mut self.curr_node_visitor else { return None };
-        match visitor.next() {
-            None => {
-                // Proceed to next edge unless we've already reached the end of the edges.
-                if self.curr_edge < self.root.edges.len() {
-                    self.curr_edge += 1;
-                }
-                // If there are no edges left, we signal end of iteration.
-                if self.curr_edge == self.root.edges.len() {
-                    self.curr_node = None;
-                    self.curr_node_visitor = None;
-                    return None;
-                }
-
-                let curr_node = &self.root.edges[self.curr_edge].child_node;
-                self.curr_node = Some(curr_node);
-                let curr_node_visitor = WordCharTreeNodeVisitor::new(curr_node);
-                self.curr_node_visitor = Some(curr_node_visitor);
-                (self.curr_node_visitor.as_mut().unwrap()).next()
-            }
-            Some(w) => Some(w),
-        }
-    }
-}
+/// An edge of a word char tree, labeled with a lowercase [`char`]
+#[cfg(any(feature = "std", test))]
+type WordCharTreeEdge<'a, W> = WordTreeEdge<'a, char, W>;
 
-struct WordCharTreeNodeVisitor<'a, W> {
-    node: &'a WordCharTreeNode<'a, W>,
-    has_visited_own_node: bool,
-    has_initialized_children: bool,
-    curr_edge: usize,
-    curr_node: Option<&'a WordCharTreeNode<'a, W>>,
-    curr_node_visitor: Option<Box<WordCharTreeNodeVisitor<'a, W>>>,
-}
-
-impl<'a, W> WordCharTreeNodeVisitor<'a, W> {
-    fn new(node: &'a WordCharTreeNode<'a, W>) -> Self {
-        Self {
-            node,
-            has_visited_own_node: false,
-            has_initialized_children: false,
-            curr_edge: 0,
-            curr_node: None,
-            curr_node_visitor: None,
-        }
-    }
-}
-
-impl<'a, W> Iterator for WordCharTreeNodeVisitor<'a, W> {
-    type Item = &'a W;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // Visit own node before visiting children
-        if !self.has_visited_own_node {
-            self.has_visited_own_node = true;
-            if self.node.word.is_some() {
-                return self.node.word.as_ref();
-            }
-        }
-
-        if !self.has_initialized_children {
-            self.has_initialized_children = true;
-            if self.node.edges.is_empty() {
-                self.curr_node = None;
-                self.curr_node_visitor = None;
-            } else {
-                let curr_node = &self.node.edges[0].child_node;
-                self.curr_node = Some(curr_node);
-                self.curr_node_visitor = Some(Box::new(WordCharTreeNodeVisitor::new(curr_node)));
-            }
-        }
-
-        let Some(visitor) = &mut self.curr_node_visitor else { return None };
-        match visitor.next() {
-            None => {
-                // Proceed to next edge unless we've already reached the end of the edges.
-                if self.curr_edge < self.node.edges.len() {
-                    self.curr_edge += 1;
-                }
-                // If there are no edges left, signal to parent that subtree is done.
-                if self.curr_edge == self.node.edges.len() {
-                    self.curr_node = None;
-                    self.curr_node_visitor = None;
-                    return None;
-                }
-
-                let curr_node = &self.node.edges[self.curr_edge].child_node;
-                self.curr_node = Some(curr_node);
-                let curr_node_visitor = WordCharTreeNodeVisitor::new(curr_node);
-                self.curr_node_visitor = Some(Box::new(curr_node_visitor));
-                (self.curr_node_visitor.as_mut().unwrap()).next()
-            }
-            Some(w) => Some(w),
-        }
-    }
-}
+/// A node of a word char tree
+#[cfg(any(feature = "std", test))]
+type WordCharTreeNode<'a, W> = WordTreeNode<'a, char, W>;
 
 /// The root node of a tree, where the edges are [`char`]s and the nodes are `Option<W>` words
 ///
+/// A word char tree is a [`WordTree`] with `char` labels, together with the
+/// [`CaseFolding`] policy and the [`Normalization`] that map strings to chars.
+///
 /// Regarding the `Option<W>` words in the tree, see in particular the following:
 /// - [`Self::is_fully_well_formed`]
 /// - [`Self::is_suitable_for_iterative_char_search`]
 /// - [`Self::words`]
 pub struct WordCharTreeRootNode<'a, W> {
-    edges: &'a [WordCharTreeEdge<'a, W>],
+    tree: WordTree<'a, char, W>,
+    case_folding: CaseFolding,
+    normalization: Normalization,
 }
 
-impl<W> WordCharTreeRootNode<'_, W> {
+impl<'a, W> WordCharTreeRootNode<'a, W> {
+    /// Get the underlying [`WordTree`] with `char` labels
+    pub fn as_word_tree(&self) -> &WordTree<'a, char, W> {
+        &self.tree
+    }
+    /// Get the case folding policy that maps the [`char`]s of strings to the edges of the tree
+    ///
+    /// The policy is set when the tree is built (see [`WordCharTreeBuilder::with_case_folding`]),
+    /// and is used for every lookup in the tree.
+    pub fn case_folding(&self) -> CaseFolding {
+        self.case_folding
+    }
+    /// Get the Unicode normalization that is applied to strings before case folding
+    ///
+    /// The normalization is set when the tree is built
+    /// (see [`WordCharTreeBuilder::with_normalization`]), and is used for every lookup in the tree.
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
     /// Get the max depth of the tree
     ///
     /// Measured in number of lowercase [`char`] edges from the root node
@@ -147,12 +105,8 @@ impl<W> WordCharTreeRootNode<'_, W> {
     ///
     /// In a [fully well-formed](`Self::is_fully_well_formed`) word char tree, this depth
     /// corresponds to the length in `char`s of the longest word in the tree.
-    pub fn get_max_depth(&self) -> usize {
-        self.edges
-            .iter()
-            .map(|edge| edge.get_max_depth(0))
-            .max()
-            .unwrap_or(0)
+    pub const fn get_max_depth(&self) -> usize {
+        self.tree.get_max_depth()
     }
     /// The tree is *fully well-formed* as long as either of the following is true:
     /// - The tree is empty, or
@@ -163,11 +117,8 @@ impl<W> WordCharTreeRootNode<'_, W> {
     /// - Non-leaf nodes are allowed to have `word: Some(W)`.
     ///
     /// The tree is NOT *fully well-formed* if any of the leaf nodes have `word: None`.
-    pub fn is_fully_well_formed(&self) -> bool {
-        self.edges
-            .iter()
-            .map(|edge| edge.is_fully_well_formed())
-            .all(|b| b)
+    pub const fn is_fully_well_formed(&self) -> bool {
+        self.tree.is_fully_well_formed()
     }
     /// The tree is *suitable for iterative char search* for words `W` if the following is true:
     /// - Every non-leaf node has `word: None`.
@@ -188,112 +139,156 @@ impl<W> WordCharTreeRootNode<'_, W> {
     ///    You wanted to find the word `army`.
     /// 5. In this case, it was not appropriate to use iterative char search,
     ///    because the wordlist was not suitable for iterative char search.
-    pub fn is_suitable_for_iterative_char_search(&self) -> bool {
-        self.edges
-            .iter()
-            .map(|edge| edge.is_suitable_for_iterative_char_search())
-            .all(|b| b)
+    pub const fn is_suitable_for_iterative_char_search(&self) -> bool {
+        self.tree.is_suitable_for_iterative_search()
     }
     /// Returns an iterator over the words `W` of a word char tree
-    pub fn words(&self) -> Words<W> {
-        Words::new(Iter::boxed(self))
+    pub fn words(&self) -> WordTreeWords<'_, 'a, char, W> {
+        self.tree.words()
     }
-}
-
-struct WordCharTreeEdge<'a, W> {
-    char_lowercase: char,
-    idx_range: RangeInclusive<usize>,
-    child_node: WordCharTreeNode<'a, W>,
-}
-
-impl<W> WordCharTreeEdge<'_, W> {
-    fn get_max_depth(&self, depth_at_parent_node: usize) -> usize {
-        self.child_node.get_max_depth(depth_at_parent_node)
+    /// Returns an iterator over the words `W` of a word char tree, shortest first
+    ///
+    /// The length of a word is its number of [`char`] edges. Words of the same length
+    /// are visited in alphabetical order, same as in [`Self::words`].
+    #[cfg(feature = "std")]
+    pub fn words_bfs(&self) -> WordTreeWordsByLen<'_, 'a, char, W> {
+        self.tree.words_bfs()
     }
-    fn is_fully_well_formed(&self) -> bool {
-        self.child_node.is_fully_well_formed()
+    /// Returns an iterator over the words `W` that are exactly `len` [`char`] edges long,
+    /// in alphabetical order
+    #[cfg(feature = "std")]
+    pub fn words_of_len(&self, len: usize) -> WordTreeWordsByLen<'_, 'a, char, W> {
+        self.tree.words_of_len(len)
     }
-    fn is_suitable_for_iterative_char_search(&self) -> bool {
-        self.child_node.is_suitable_for_iterative_char_search()
+    /// Get a read-only reference to the root node of the tree, for custom traversals
+    ///
+    /// The labels of the edges are the [`char`]s after case folding and normalization,
+    /// so [`NodeRef::child`] must be given chars that are already mapped.
+    pub fn root(&self) -> NodeRef<'_, 'a, char, W> {
+        self.tree.root()
+    }
+    /// Get a cursor at the root node of the tree, for custom traversals
+    ///
+    /// Same as for [`Self::root`], the cursor follows chars that are already mapped.
+    #[cfg(feature = "std")]
+    pub fn cursor(&self) -> Cursor<'_, 'a, char, W> {
+        self.tree.cursor()
+    }
+    /// Get the number of words in the tree
+    ///
+    /// Computed from the `idx_range`s of the edges of the root node.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+    /// Returns `true` if the tree contains no words
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+    /// Get the number of nodes in the tree, including the root node
+    pub fn node_count(&self) -> usize {
+        self.tree.node_count()
+    }
+    /// Look up the word `W` for the given string
+    ///
+    /// The [`char`]s of the string are mapped by the [case folding](`Self::case_folding`)
+    /// policy and the [normalization](`Self::normalization`) of the tree
+    /// before following the edge for each of them.
+    pub fn get(&self, word: &str) -> Option<&W> {
+        self.tree.get(self.edge_chars(word))
+    }
+    /// Look up the index of the word for the given string
+    ///
+    /// The index of a word is the position of the word in the order of [`Self::words`].
+    pub fn get_index(&self, word: &str) -> Option<usize> {
+        self.tree.get_index(self.edge_chars(word))
+    }
+    /// Look up the word `W` at the given index
+    ///
+    /// The search descends along the edges whose `idx_range` contains the index.
+    pub fn get_by_index(&self, idx: usize) -> Option<&W> {
+        self.tree.get_by_index(idx)
+    }
+    /// Get the range of indices of the words that start with the given prefix
+    ///
+    /// The empty prefix matches every word in the tree.
+    pub fn prefix_range(&self, prefix: &str) -> Option<RangeInclusive<usize>> {
+        self.tree.prefix_range(self.edge_chars(prefix))
+    }
+    /// Returns an iterator over the words `W` that start with the given prefix
+    pub fn words_with_prefix(&self, prefix: &str) -> WordTreeWords<'_, 'a, char, W> {
+        self.tree.words_with_prefix(self.edge_chars(prefix))
+    }
+    /// Map the chars of a string to the chars of the edges along its path in the tree
+    pub(crate) fn edge_chars<'s>(&self, s: &'s str) -> FoldedChars<Normalized<Chars<'s>>> {
+        edge_chars(s, self.case_folding, self.normalization)
+    }
+    /// Panic if the trees map strings to chars differently,
+    /// so that their edges cannot be compared
+    #[cfg(feature = "std")]
+    fn assert_same_mapping<V>(&self, other: &WordCharTreeRootNode<'_, V>) {
+        assert_eq!(
+            (self.case_folding, self.normalization),
+            (other.case_folding, other.normalization),
+            "trees map strings to chars differently"
+        );
     }
 }
 
-struct WordCharTreeNode<'a, W> {
-    word: Option<W>,
-    edges: &'a [WordCharTreeEdge<'a, W>],
-}
-
-impl<W> WordCharTreeNode<'_, W> {
-    fn get_max_depth(&self, depth_at_parent_edge: usize) -> usize {
-        let curr_depth = depth_at_parent_edge + 1;
-        self.edges
-            .iter()
-            .map(|edge| edge.get_max_depth(curr_depth))
-            .max()
-            .unwrap_or(curr_depth)
-    }
-    fn is_fully_well_formed(&self) -> bool {
-        self.edges
-            .iter()
-            .map(|edge| edge.is_fully_well_formed())
-            .all(|b| b)
-    }
-    fn is_suitable_for_iterative_char_search(&self) -> bool {
-        if self.edges.is_empty() {
-            true
-        } else if self.word.is_some() {
-            false
-        } else {
-            self.edges
-                .iter()
-                .map(|edge| edge.is_suitable_for_iterative_char_search())
-                .all(|b| b)
-        }
+impl<'a, W> From<WordCharTreeRootNode<'a, W>> for WordTree<'a, char, W> {
+    fn from(root: WordCharTreeRootNode<'a, W>) -> Self {
+        root.tree
     }
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
     use test_case::test_case;
 
-    #[derive(Debug, PartialEq)]
+    /// Create a tree where the word of each string is the string itself
+    #[cfg(feature = "std")]
+    pub fn example_tree(words: &[&str]) -> WordCharTreeRootNode<'static, String> {
+        words.iter().map(|&w| (w, w.to_string())).collect()
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ExampleWords1 {
         Get,
         Give,
         Go,
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ExampleWords2 {
         Arm,
         Army,
         Man,
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ExampleWords3 {
         A,
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ExampleWords4 {
         An,
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ExampleWords5 {
         Ant,
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ExampleWords6 {
         A,
         An,
         Ant,
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
+    #[allow(clippy::upper_case_acronyms)]
     pub enum ExampleWords7 {
         Ant,
         Art,
@@ -315,531 +310,583 @@ mod test {
 
     /// A well-formed example empty wordlist
     /// Suitable for iterative char search (although it would be rather pointless in this case :P)
-    pub const EXAMPLE_WORDLIST_EMPTY: WordCharTreeRootNode<()> =
-        WordCharTreeRootNode { edges: &[] };
+    pub const EXAMPLE_WORDLIST_EMPTY: WordCharTreeRootNode<()> = WordCharTreeRootNode {
+        tree: WordTree {
+            edges: Edges::Borrowed(&[]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
+    };
 
     /// A well-formed example wordlist
     /// Suitable for iterative char search
     pub const EXAMPLE_WORDLIST_1: WordCharTreeRootNode<ExampleWords1> = WordCharTreeRootNode {
-        edges: &[WordCharTreeEdge {
-            char_lowercase: 'g',
-            idx_range: 0..=2,
-            child_node: WordCharTreeNode {
-                word: None,
-                edges: &[
-                    WordCharTreeEdge {
-                        char_lowercase: 'e',
-                        idx_range: 0..=0,
-                        child_node: WordCharTreeNode {
-                            word: None,
-                            edges: &[WordCharTreeEdge {
-                                char_lowercase: 't',
-                                idx_range: 0..=0,
-                                child_node: WordCharTreeNode {
-                                    word: Some(ExampleWords1::Get),
-                                    edges: &[],
-                                },
-                            }],
+        tree: WordTree {
+            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                label: 'g',
+                idx_range: 0..=2,
+                child_node: WordCharTreeNode {
+                    word: None,
+                    edges: Edges::Borrowed(&[
+                        WordCharTreeEdge {
+                            label: 'e',
+                            idx_range: 0..=0,
+                            child_node: WordCharTreeNode {
+                                word: None,
+                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                    label: 't',
+                                    idx_range: 0..=0,
+                                    child_node: WordCharTreeNode {
+                                        word: Some(ExampleWords1::Get),
+                                        edges: Edges::Borrowed(&[]),
+                                    },
+                                }]),
+                            },
                         },
-                    },
-                    WordCharTreeEdge {
-                        char_lowercase: 'i',
-                        idx_range: 1..=1,
-                        child_node: WordCharTreeNode {
-                            word: None,
-                            edges: &[WordCharTreeEdge {
-                                char_lowercase: 'v',
-                                idx_range: 1..=1,
-                                child_node: WordCharTreeNode {
-                                    word: None,
-                                    edges: &[WordCharTreeEdge {
-                                        char_lowercase: 'e',
-                                        idx_range: 1..=1,
-                                        child_node: WordCharTreeNode {
-                                            word: Some(ExampleWords1::Give),
-                                            edges: &[],
-                                        },
-                                    }],
-                                },
-                            }],
+                        WordCharTreeEdge {
+                            label: 'i',
+                            idx_range: 1..=1,
+                            child_node: WordCharTreeNode {
+                                word: None,
+                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                    label: 'v',
+                                    idx_range: 1..=1,
+                                    child_node: WordCharTreeNode {
+                                        word: None,
+                                        edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                            label: 'e',
+                                            idx_range: 1..=1,
+                                            child_node: WordCharTreeNode {
+                                                word: Some(ExampleWords1::Give),
+                                                edges: Edges::Borrowed(&[]),
+                                            },
+                                        }]),
+                                    },
+                                }]),
+                            },
                         },
-                    },
-                    WordCharTreeEdge {
-                        char_lowercase: 'o',
-                        idx_range: 2..=2,
-                        child_node: WordCharTreeNode {
-                            word: Some(ExampleWords1::Go),
-                            edges: &[],
+                        WordCharTreeEdge {
+                            label: 'o',
+                            idx_range: 2..=2,
+                            child_node: WordCharTreeNode {
+                                word: Some(ExampleWords1::Go),
+                                edges: Edges::Borrowed(&[]),
+                            },
                         },
-                    },
-                ],
-            },
-        }],
+                    ]),
+                },
+            }]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
     };
 
     /// A well-formed example wordlist
     /// Not suitable for iterative char search
     pub const EXAMPLE_WORDLIST_2: WordCharTreeRootNode<ExampleWords2> = WordCharTreeRootNode {
-        edges: &[
-            WordCharTreeEdge {
-                char_lowercase: 'a',
-                idx_range: 0..=1,
-                child_node: WordCharTreeNode {
-                    word: None,
-                    edges: &[WordCharTreeEdge {
-                        char_lowercase: 'r',
-                        idx_range: 0..=1,
-                        child_node: WordCharTreeNode {
-                            word: None,
-                            edges: &[WordCharTreeEdge {
-                                char_lowercase: 'm',
-                                idx_range: 0..=1,
-                                child_node: WordCharTreeNode {
-                                    word: Some(ExampleWords2::Arm),
-                                    edges: &[WordCharTreeEdge {
-                                        char_lowercase: 'y',
-                                        idx_range: 1..=1,
-                                        child_node: WordCharTreeNode {
-                                            word: Some(ExampleWords2::Army),
-                                            edges: &[],
-                                        },
-                                    }],
-                                },
-                            }],
-                        },
-                    }],
+        tree: WordTree {
+            edges: Edges::Borrowed(&[
+                WordCharTreeEdge {
+                    label: 'a',
+                    idx_range: 0..=1,
+                    child_node: WordCharTreeNode {
+                        word: None,
+                        edges: Edges::Borrowed(&[WordCharTreeEdge {
+                            label: 'r',
+                            idx_range: 0..=1,
+                            child_node: WordCharTreeNode {
+                                word: None,
+                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                    label: 'm',
+                                    idx_range: 0..=1,
+                                    child_node: WordCharTreeNode {
+                                        word: Some(ExampleWords2::Arm),
+                                        edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                            label: 'y',
+                                            idx_range: 1..=1,
+                                            child_node: WordCharTreeNode {
+                                                word: Some(ExampleWords2::Army),
+                                                edges: Edges::Borrowed(&[]),
+                                            },
+                                        }]),
+                                    },
+                                }]),
+                            },
+                        }]),
+                    },
                 },
-            },
-            WordCharTreeEdge {
-                char_lowercase: 'm',
-                idx_range: 2..=2,
-                child_node: WordCharTreeNode {
-                    word: None,
-                    edges: &[WordCharTreeEdge {
-                        char_lowercase: 'a',
-                        idx_range: 2..=2,
-                        child_node: WordCharTreeNode {
-                            word: None,
-                            edges: &[WordCharTreeEdge {
-                                char_lowercase: 'n',
-                                idx_range: 2..=2,
-                                child_node: WordCharTreeNode {
-                                    word: Some(ExampleWords2::Man),
-                                    edges: &[],
-                                },
-                            }],
-                        },
-                    }],
+                WordCharTreeEdge {
+                    label: 'm',
+                    idx_range: 2..=2,
+                    child_node: WordCharTreeNode {
+                        word: None,
+                        edges: Edges::Borrowed(&[WordCharTreeEdge {
+                            label: 'a',
+                            idx_range: 2..=2,
+                            child_node: WordCharTreeNode {
+                                word: None,
+                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                    label: 'n',
+                                    idx_range: 2..=2,
+                                    child_node: WordCharTreeNode {
+                                        word: Some(ExampleWords2::Man),
+                                        edges: Edges::Borrowed(&[]),
+                                    },
+                                }]),
+                            },
+                        }]),
+                    },
                 },
-            },
-        ],
+            ]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
     };
 
     /// A well-formed example wordlist
     /// Suitable for iterative char search
     pub const EXAMPLE_WORDLIST_3: WordCharTreeRootNode<ExampleWords3> = WordCharTreeRootNode {
-        edges: &[WordCharTreeEdge {
-            char_lowercase: 'a',
-            idx_range: 0..=0,
-            child_node: WordCharTreeNode {
-                word: Some(ExampleWords3::A),
-                edges: &[],
-            },
-        }],
+        tree: WordTree {
+            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                label: 'a',
+                idx_range: 0..=0,
+                child_node: WordCharTreeNode {
+                    word: Some(ExampleWords3::A),
+                    edges: Edges::Borrowed(&[]),
+                },
+            }]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
     };
 
     /// A well-formed example wordlist
     /// Suitable for iterative char search
     pub const EXAMPLE_WORDLIST_4: WordCharTreeRootNode<ExampleWords4> = WordCharTreeRootNode {
-        edges: &[WordCharTreeEdge {
-            char_lowercase: 'a',
-            idx_range: 0..=0,
-            child_node: WordCharTreeNode {
-                word: None,
-                edges: &[WordCharTreeEdge {
-                    char_lowercase: 'n',
-                    idx_range: 0..=0,
-                    child_node: WordCharTreeNode {
-                        word: Some(ExampleWords4::An),
-                        edges: &[],
-                    },
-                }],
-            },
-        }],
+        tree: WordTree {
+            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                label: 'a',
+                idx_range: 0..=0,
+                child_node: WordCharTreeNode {
+                    word: None,
+                    edges: Edges::Borrowed(&[WordCharTreeEdge {
+                        label: 'n',
+                        idx_range: 0..=0,
+                        child_node: WordCharTreeNode {
+                            word: Some(ExampleWords4::An),
+                            edges: Edges::Borrowed(&[]),
+                        },
+                    }]),
+                },
+            }]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
     };
 
     /// A well-formed example wordlist
     /// Suitable for iterative char search
     pub const EXAMPLE_WORDLIST_5: WordCharTreeRootNode<ExampleWords5> = WordCharTreeRootNode {
-        edges: &[WordCharTreeEdge {
-            char_lowercase: 'a',
-            idx_range: 0..=0,
-            child_node: WordCharTreeNode {
-                word: None,
-                edges: &[WordCharTreeEdge {
-                    char_lowercase: 'n',
-                    idx_range: 0..=0,
-                    child_node: WordCharTreeNode {
-                        word: None,
-                        edges: &[WordCharTreeEdge {
-                            char_lowercase: 't',
-                            idx_range: 0..=0,
-                            child_node: WordCharTreeNode {
-                                word: Some(ExampleWords5::Ant),
-                                edges: &[],
-                            },
-                        }],
-                    },
-                }],
-            },
-        }],
+        tree: WordTree {
+            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                label: 'a',
+                idx_range: 0..=0,
+                child_node: WordCharTreeNode {
+                    word: None,
+                    edges: Edges::Borrowed(&[WordCharTreeEdge {
+                        label: 'n',
+                        idx_range: 0..=0,
+                        child_node: WordCharTreeNode {
+                            word: None,
+                            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                label: 't',
+                                idx_range: 0..=0,
+                                child_node: WordCharTreeNode {
+                                    word: Some(ExampleWords5::Ant),
+                                    edges: Edges::Borrowed(&[]),
+                                },
+                            }]),
+                        },
+                    }]),
+                },
+            }]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
     };
 
     /// A well-formed example wordlist
     /// Not suitable for iterative char search
     pub const EXAMPLE_WORDLIST_6: WordCharTreeRootNode<ExampleWords6> = WordCharTreeRootNode {
-        edges: &[WordCharTreeEdge {
-            char_lowercase: 'a',
-            idx_range: 0..=2,
-            child_node: WordCharTreeNode {
-                word: Some(ExampleWords6::A),
-                edges: &[WordCharTreeEdge {
-                    char_lowercase: 'n',
-                    idx_range: 1..=2,
-                    child_node: WordCharTreeNode {
-                        word: Some(ExampleWords6::An),
-                        edges: &[WordCharTreeEdge {
-                            char_lowercase: 't',
-                            idx_range: 2..=2,
-                            child_node: WordCharTreeNode {
-                                word: Some(ExampleWords6::Ant),
-                                edges: &[],
-                            },
-                        }],
-                    },
-                }],
-            },
-        }],
+        tree: WordTree {
+            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                label: 'a',
+                idx_range: 0..=2,
+                child_node: WordCharTreeNode {
+                    word: Some(ExampleWords6::A),
+                    edges: Edges::Borrowed(&[WordCharTreeEdge {
+                        label: 'n',
+                        idx_range: 1..=2,
+                        child_node: WordCharTreeNode {
+                            word: Some(ExampleWords6::An),
+                            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                label: 't',
+                                idx_range: 2..=2,
+                                child_node: WordCharTreeNode {
+                                    word: Some(ExampleWords6::Ant),
+                                    edges: Edges::Borrowed(&[]),
+                                },
+                            }]),
+                        },
+                    }]),
+                },
+            }]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
     };
 
     /// A well-formed example wordlist
     /// Not suitable for iterative char search
     pub const EXAMPLE_WORDLIST_7: WordCharTreeRootNode<ExampleWords7> = WordCharTreeRootNode {
-        edges: &[
-            WordCharTreeEdge {
-                char_lowercase: 'a',
-                idx_range: 0..=1,
-                child_node: WordCharTreeNode {
-                    word: None,
-                    edges: &[
-                        WordCharTreeEdge {
-                            char_lowercase: 'n',
-                            idx_range: 0..=0,
-                            child_node: WordCharTreeNode {
-                                word: None,
-                                edges: &[WordCharTreeEdge {
-                                    char_lowercase: 't',
-                                    idx_range: 0..=0,
-                                    child_node: WordCharTreeNode {
-                                        word: Some(ExampleWords7::Ant),
-                                        edges: &[],
-                                    },
-                                }],
+        tree: WordTree {
+            edges: Edges::Borrowed(&[
+                WordCharTreeEdge {
+                    label: 'a',
+                    idx_range: 0..=1,
+                    child_node: WordCharTreeNode {
+                        word: None,
+                        edges: Edges::Borrowed(&[
+                            WordCharTreeEdge {
+                                label: 'n',
+                                idx_range: 0..=0,
+                                child_node: WordCharTreeNode {
+                                    word: None,
+                                    edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                        label: 't',
+                                        idx_range: 0..=0,
+                                        child_node: WordCharTreeNode {
+                                            word: Some(ExampleWords7::Ant),
+                                            edges: Edges::Borrowed(&[]),
+                                        },
+                                    }]),
+                                },
                             },
-                        },
-                        WordCharTreeEdge {
-                            char_lowercase: 'r',
-                            idx_range: 1..=1,
-                            child_node: WordCharTreeNode {
-                                word: None,
-                                edges: &[WordCharTreeEdge {
-                                    char_lowercase: 't',
-                                    idx_range: 1..=1,
-                                    child_node: WordCharTreeNode {
-                                        word: Some(ExampleWords7::Art),
-                                        edges: &[],
-                                    },
-                                }],
+                            WordCharTreeEdge {
+                                label: 'r',
+                                idx_range: 1..=1,
+                                child_node: WordCharTreeNode {
+                                    word: None,
+                                    edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                        label: 't',
+                                        idx_range: 1..=1,
+                                        child_node: WordCharTreeNode {
+                                            word: Some(ExampleWords7::Art),
+                                            edges: Edges::Borrowed(&[]),
+                                        },
+                                    }]),
+                                },
                             },
-                        },
-                    ],
+                        ]),
+                    },
                 },
-            },
-            WordCharTreeEdge {
-                char_lowercase: 'i',
-                idx_range: 2..=2,
-                child_node: WordCharTreeNode {
-                    word: Some(ExampleWords7::I),
-                    edges: &[],
+                WordCharTreeEdge {
+                    label: 'i',
+                    idx_range: 2..=2,
+                    child_node: WordCharTreeNode {
+                        word: Some(ExampleWords7::I),
+                        edges: Edges::Borrowed(&[]),
+                    },
                 },
-            },
-            WordCharTreeEdge {
-                char_lowercase: 'm',
-                idx_range: 3..=8,
-                child_node: WordCharTreeNode {
-                    word: None,
-                    edges: &[
-                        WordCharTreeEdge {
-                            char_lowercase: 'a',
-                            idx_range: 3..=7,
-                            child_node: WordCharTreeNode {
-                                word: None,
-                                edges: &[
-                                    WordCharTreeEdge {
-                                        char_lowercase: 'i',
-                                        idx_range: 3..=3,
-                                        child_node: WordCharTreeNode {
-                                            word: None,
-                                            edges: &[WordCharTreeEdge {
-                                                char_lowercase: 'n',
-                                                idx_range: 3..=3,
-                                                child_node: WordCharTreeNode {
-                                                    word: Some(ExampleWords7::Main),
-                                                    edges: &[],
-                                                },
-                                            }],
-                                        },
-                                    },
-                                    WordCharTreeEdge {
-                                        char_lowercase: 'n',
-                                        idx_range: 4..=6,
-                                        child_node: WordCharTreeNode {
-                                            word: Some(ExampleWords7::Man),
-                                            edges: &[
-                                                WordCharTreeEdge {
-                                                    char_lowercase: 'e',
-                                                    idx_range: 5..=5,
+                WordCharTreeEdge {
+                    label: 'm',
+                    idx_range: 3..=8,
+                    child_node: WordCharTreeNode {
+                        word: None,
+                        edges: Edges::Borrowed(&[
+                            WordCharTreeEdge {
+                                label: 'a',
+                                idx_range: 3..=7,
+                                child_node: WordCharTreeNode {
+                                    word: None,
+                                    edges: Edges::Borrowed(&[
+                                        WordCharTreeEdge {
+                                            label: 'i',
+                                            idx_range: 3..=3,
+                                            child_node: WordCharTreeNode {
+                                                word: None,
+                                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                    label: 'n',
+                                                    idx_range: 3..=3,
                                                     child_node: WordCharTreeNode {
-                                                        word: Some(ExampleWords7::Mane),
-                                                        edges: &[],
+                                                        word: Some(ExampleWords7::Main),
+                                                        edges: Edges::Borrowed(&[]),
                                                     },
-                                                },
-                                                WordCharTreeEdge {
-                                                    char_lowercase: 'g',
-                                                    idx_range: 6..=6,
+                                                }]),
+                                            },
+                                        },
+                                        WordCharTreeEdge {
+                                            label: 'n',
+                                            idx_range: 4..=6,
+                                            child_node: WordCharTreeNode {
+                                                word: Some(ExampleWords7::Man),
+                                                edges: Edges::Borrowed(&[
+                                                    WordCharTreeEdge {
+                                                        label: 'e',
+                                                        idx_range: 5..=5,
+                                                        child_node: WordCharTreeNode {
+                                                            word: Some(ExampleWords7::Mane),
+                                                            edges: Edges::Borrowed(&[]),
+                                                        },
+                                                    },
+                                                    WordCharTreeEdge {
+                                                        label: 'g',
+                                                        idx_range: 6..=6,
+                                                        child_node: WordCharTreeNode {
+                                                            word: None,
+                                                            edges: Edges::Borrowed(&[
+                                                                WordCharTreeEdge {
+                                                                    label: 'o',
+                                                                    idx_range: 6..=6,
+                                                                    child_node: WordCharTreeNode {
+                                                                        word: Some(
+                                                                            ExampleWords7::Mango,
+                                                                        ),
+                                                                        edges: Edges::Borrowed(&[]),
+                                                                    },
+                                                                },
+                                                            ]),
+                                                        },
+                                                    },
+                                                ]),
+                                            },
+                                        },
+                                        WordCharTreeEdge {
+                                            label: 'r',
+                                            idx_range: 7..=7,
+                                            child_node: WordCharTreeNode {
+                                                word: None,
+                                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                    label: 'e',
+                                                    idx_range: 7..=7,
                                                     child_node: WordCharTreeNode {
-                                                        word: None,
-                                                        edges: &[WordCharTreeEdge {
-                                                            char_lowercase: 'o',
-                                                            idx_range: 6..=6,
-                                                            child_node: WordCharTreeNode {
-                                                                word: Some(ExampleWords7::Mango),
-                                                                edges: &[],
-                                                            },
-                                                        }],
+                                                        word: Some(ExampleWords7::Mare),
+                                                        edges: Edges::Borrowed(&[]),
                                                     },
-                                                },
-                                            ],
+                                                }]),
+                                            },
                                         },
-                                    },
-                                    WordCharTreeEdge {
-                                        char_lowercase: 'r',
-                                        idx_range: 7..=7,
+                                    ]),
+                                },
+                            },
+                            WordCharTreeEdge {
+                                label: 'o',
+                                idx_range: 8..=8,
+                                child_node: WordCharTreeNode {
+                                    word: None,
+                                    edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                        label: 'r',
+                                        idx_range: 8..=8,
                                         child_node: WordCharTreeNode {
                                             word: None,
-                                            edges: &[WordCharTreeEdge {
-                                                char_lowercase: 'e',
-                                                idx_range: 7..=7,
+                                            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                label: 'e',
+                                                idx_range: 8..=8,
                                                 child_node: WordCharTreeNode {
-                                                    word: Some(ExampleWords7::Mare),
-                                                    edges: &[],
+                                                    word: Some(ExampleWords7::More),
+                                                    edges: Edges::Borrowed(&[]),
                                                 },
-                                            }],
+                                            }]),
                                         },
-                                    },
-                                ],
+                                    }]),
+                                },
                             },
-                        },
-                        WordCharTreeEdge {
-                            char_lowercase: 'o',
-                            idx_range: 8..=8,
+                        ]),
+                    },
+                },
+                WordCharTreeEdge {
+                    label: 'x',
+                    idx_range: 9..=10,
+                    child_node: WordCharTreeNode {
+                        word: None,
+                        edges: Edges::Borrowed(&[WordCharTreeEdge {
+                            label: 'r',
+                            idx_range: 9..=10,
                             child_node: WordCharTreeNode {
                                 word: None,
-                                edges: &[WordCharTreeEdge {
-                                    char_lowercase: 'r',
-                                    idx_range: 8..=8,
+                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                    label: 'a',
+                                    idx_range: 9..=10,
                                     child_node: WordCharTreeNode {
                                         word: None,
-                                        edges: &[WordCharTreeEdge {
-                                            char_lowercase: 'e',
-                                            idx_range: 8..=8,
-                                            child_node: WordCharTreeNode {
-                                                word: Some(ExampleWords7::More),
-                                                edges: &[],
+                                        edges: Edges::Borrowed(&[
+                                            WordCharTreeEdge {
+                                                label: 'm',
+                                                idx_range: 9..=9,
+                                                child_node: WordCharTreeNode {
+                                                    word: Some(ExampleWords7::XRAM),
+                                                    edges: Edges::Borrowed(&[]),
+                                                },
                                             },
-                                        }],
+                                            WordCharTreeEdge {
+                                                label: 'y',
+                                                idx_range: 10..=10,
+                                                child_node: WordCharTreeNode {
+                                                    word: Some(ExampleWords7::XRay),
+                                                    edges: Edges::Borrowed(&[]),
+                                                },
+                                            },
+                                        ]),
                                     },
-                                }],
+                                }]),
                             },
-                        },
-                    ],
+                        }]),
+                    },
                 },
-            },
-            WordCharTreeEdge {
-                char_lowercase: 'x',
-                idx_range: 9..=10,
-                child_node: WordCharTreeNode {
-                    word: None,
-                    edges: &[WordCharTreeEdge {
-                        char_lowercase: 'r',
-                        idx_range: 9..=10,
-                        child_node: WordCharTreeNode {
-                            word: None,
-                            edges: &[WordCharTreeEdge {
-                                char_lowercase: 'a',
-                                idx_range: 9..=10,
+                WordCharTreeEdge {
+                    label: 'z',
+                    idx_range: 11..=15,
+                    child_node: WordCharTreeNode {
+                        word: None,
+                        edges: Edges::Borrowed(&[
+                            WordCharTreeEdge {
+                                label: 'e',
+                                idx_range: 11..=12,
                                 child_node: WordCharTreeNode {
                                     word: None,
-                                    edges: &[
+                                    edges: Edges::Borrowed(&[
                                         WordCharTreeEdge {
-                                            char_lowercase: 'm',
-                                            idx_range: 9..=9,
+                                            label: 'b',
+                                            idx_range: 11..=11,
                                             child_node: WordCharTreeNode {
-                                                word: Some(ExampleWords7::XRAM),
-                                                edges: &[],
+                                                word: None,
+                                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                    label: 'r',
+                                                    idx_range: 11..=11,
+                                                    child_node: WordCharTreeNode {
+                                                        word: None,
+                                                        edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                            label: 'a',
+                                                            idx_range: 11..=11,
+                                                            child_node: WordCharTreeNode {
+                                                                word: Some(ExampleWords7::Zebra),
+                                                                edges: Edges::Borrowed(&[]),
+                                                            },
+                                                        }]),
+                                                    },
+                                                }]),
                                             },
                                         },
                                         WordCharTreeEdge {
-                                            char_lowercase: 'y',
-                                            idx_range: 10..=10,
+                                            label: 'r',
+                                            idx_range: 12..=12,
                                             child_node: WordCharTreeNode {
-                                                word: Some(ExampleWords7::XRay),
-                                                edges: &[],
+                                                word: None,
+                                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                    label: 'o',
+                                                    idx_range: 12..=12,
+                                                    child_node: WordCharTreeNode {
+                                                        word: Some(ExampleWords7::Zero),
+                                                        edges: Edges::Borrowed(&[]),
+                                                    },
+                                                }]),
                                             },
                                         },
-                                    ],
+                                    ]),
                                 },
-                            }],
-                        },
-                    }],
-                },
-            },
-            WordCharTreeEdge {
-                char_lowercase: 'z',
-                idx_range: 11..=15,
-                child_node: WordCharTreeNode {
-                    word: None,
-                    edges: &[
-                        WordCharTreeEdge {
-                            char_lowercase: 'e',
-                            idx_range: 11..=12,
-                            child_node: WordCharTreeNode {
-                                word: None,
-                                edges: &[
-                                    WordCharTreeEdge {
-                                        char_lowercase: 'b',
-                                        idx_range: 11..=11,
-                                        child_node: WordCharTreeNode {
-                                            word: None,
-                                            edges: &[WordCharTreeEdge {
-                                                char_lowercase: 'r',
-                                                idx_range: 11..=11,
-                                                child_node: WordCharTreeNode {
-                                                    word: None,
-                                                    edges: &[WordCharTreeEdge {
-                                                        char_lowercase: 'a',
-                                                        idx_range: 11..=11,
-                                                        child_node: WordCharTreeNode {
-                                                            word: Some(ExampleWords7::Zebra),
-                                                            edges: &[],
-                                                        },
-                                                    }],
-                                                },
-                                            }],
-                                        },
-                                    },
-                                    WordCharTreeEdge {
-                                        char_lowercase: 'r',
-                                        idx_range: 12..=12,
+                            },
+                            WordCharTreeEdge {
+                                label: 'i',
+                                idx_range: 13..=13,
+                                child_node: WordCharTreeNode {
+                                    word: None,
+                                    edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                        label: 'n',
+                                        idx_range: 13..=13,
                                         child_node: WordCharTreeNode {
                                             word: None,
-                                            edges: &[WordCharTreeEdge {
-                                                char_lowercase: 'o',
-                                                idx_range: 12..=12,
+                                            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                label: 'c',
+                                                idx_range: 13..=13,
                                                 child_node: WordCharTreeNode {
-                                                    word: Some(ExampleWords7::Zero),
-                                                    edges: &[],
+                                                    word: Some(ExampleWords7::Zinc),
+                                                    edges: Edges::Borrowed(&[]),
                                                 },
-                                            }],
+                                            }]),
                                         },
-                                    },
-                                ],
+                                    }]),
+                                },
                             },
-                        },
-                        WordCharTreeEdge {
-                            char_lowercase: 'i',
-                            idx_range: 13..=13,
-                            child_node: WordCharTreeNode {
-                                word: None,
-                                edges: &[WordCharTreeEdge {
-                                    char_lowercase: 'n',
-                                    idx_range: 13..=13,
-                                    child_node: WordCharTreeNode {
-                                        word: None,
-                                        edges: &[WordCharTreeEdge {
-                                            char_lowercase: 'c',
-                                            idx_range: 13..=13,
+                            WordCharTreeEdge {
+                                label: 'o',
+                                idx_range: 14..=15,
+                                child_node: WordCharTreeNode {
+                                    word: None,
+                                    edges: Edges::Borrowed(&[
+                                        WordCharTreeEdge {
+                                            label: 'm',
+                                            idx_range: 14..=14,
                                             child_node: WordCharTreeNode {
-                                                word: Some(ExampleWords7::Zinc),
-                                                edges: &[],
+                                                word: None,
+                                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                    label: 'b',
+                                                    idx_range: 14..=14,
+                                                    child_node: WordCharTreeNode {
+                                                        word: None,
+                                                        edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                            label: 'i',
+                                                            idx_range: 14..=14,
+                                                            child_node: WordCharTreeNode {
+                                                                word: None,
+                                                                edges: Edges::Borrowed(&[WordCharTreeEdge {
+                                                                    label: 'e',
+                                                                    idx_range: 14..=14,
+                                                                    child_node: WordCharTreeNode {
+                                                                        word: Some(ExampleWords7::Zombie),
+                                                                        edges: Edges::Borrowed(&[]),
+                                                                    },
+                                                                }]),
+                                                            },
+                                                        }]),
+                                                    },
+                                                }]),
                                             },
-                                        }],
-                                    },
-                                }],
-                            },
-                        },
-                        WordCharTreeEdge {
-                            char_lowercase: 'o',
-                            idx_range: 14..=15,
-                            child_node: WordCharTreeNode {
-                                word: None,
-                                edges: &[
-                                    WordCharTreeEdge {
-                                        char_lowercase: 'm',
-                                        idx_range: 14..=14,
-                                        child_node: WordCharTreeNode {
-                                            word: None,
-                                            edges: &[WordCharTreeEdge {
-                                                char_lowercase: 'b',
-                                                idx_range: 14..=14,
-                                                child_node: WordCharTreeNode {
-                                                    word: None,
-                                                    edges: &[WordCharTreeEdge {
-                                                        char_lowercase: 'i',
-                                                        idx_range: 14..=14,
-                                                        child_node: WordCharTreeNode {
-                                                            word: None,
-                                                            edges: &[WordCharTreeEdge {
-                                                                char_lowercase: 'e',
-                                                                idx_range: 14..=14,
-                                                                child_node: WordCharTreeNode {
-                                                                    word: Some(
-                                                                        ExampleWords7::Zombie,
-                                                                    ),
-                                                                    edges: &[],
-                                                                },
-                                                            }],
-                                                        },
-                                                    }],
-                                                },
-                                            }],
                                         },
-                                    },
-                                    WordCharTreeEdge {
-                                        char_lowercase: 'o',
-                                        idx_range: 15..=15,
-                                        child_node: WordCharTreeNode {
-                                            word: Some(ExampleWords7::Zoo),
-                                            edges: &[],
+                                        WordCharTreeEdge {
+                                            label: 'o',
+                                            idx_range: 15..=15,
+                                            child_node: WordCharTreeNode {
+                                                word: Some(ExampleWords7::Zoo),
+                                                edges: Edges::Borrowed(&[]),
+                                            },
                                         },
-                                    },
-                                ],
+                                    ]),
+                                },
                             },
-                        },
-                    ],
+                        ]),
+                    },
+                },
+            ]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
+    };
+
+    /// A not well-formed example wordlist, with a leaf node that has no word
+    /// Suitable for iterative char search
+    pub const EXAMPLE_WORDLIST_8: WordCharTreeRootNode<()> = WordCharTreeRootNode {
+        tree: WordTree {
+            edges: Edges::Borrowed(&[WordCharTreeEdge {
+                label: 'a',
+                idx_range: 0..=0,
+                child_node: WordCharTreeNode {
+                    word: None,
+                    edges: Edges::Borrowed(&[]),
                 },
-            },
-        ],
+            }]),
+        },
+        case_folding: CaseFolding::Lowercase,
+        normalization: Normalization::None,
     };
 
     #[test_case(EXAMPLE_WORDLIST_EMPTY, 0)]
@@ -850,6 +897,7 @@ mod test {
     #[test_case(EXAMPLE_WORDLIST_5, 3)]
     #[test_case(EXAMPLE_WORDLIST_6, 3)]
     #[test_case(EXAMPLE_WORDLIST_7, 6)]
+    #[test_case(EXAMPLE_WORDLIST_8, 1)]
     fn test_positive_max_depth_value<W>(root: WordCharTreeRootNode<W>, expected_value: usize) {
         assert_eq!(root.get_max_depth(), expected_value);
     }
@@ -866,11 +914,17 @@ mod test {
         assert!(root.is_fully_well_formed());
     }
 
+    #[test_case(EXAMPLE_WORDLIST_8)]
+    fn test_negative_fully_well_formed<W>(root: WordCharTreeRootNode<W>) {
+        assert!(!root.is_fully_well_formed());
+    }
+
     #[test_case(EXAMPLE_WORDLIST_EMPTY)]
     #[test_case(EXAMPLE_WORDLIST_1)]
     #[test_case(EXAMPLE_WORDLIST_3)]
     #[test_case(EXAMPLE_WORDLIST_4)]
     #[test_case(EXAMPLE_WORDLIST_5)]
+    #[test_case(EXAMPLE_WORDLIST_8)]
     fn test_positive_suitable_iterative_char_search<W>(root: WordCharTreeRootNode<W>) {
         assert!(root.is_suitable_for_iterative_char_search());
     }
@@ -882,6 +936,12 @@ mod test {
         assert!(!root.is_suitable_for_iterative_char_search());
     }
 
+    // The checks are const fns, so they can also be asserted on consts at compile time.
+    const _: () = assert!(EXAMPLE_WORDLIST_7.get_max_depth() == 6);
+    const _: () = assert!(EXAMPLE_WORDLIST_7.is_fully_well_formed());
+    const _: () = assert!(EXAMPLE_WORDLIST_5.is_suitable_for_iterative_char_search());
+    const _: () = assert!(!EXAMPLE_WORDLIST_7.is_suitable_for_iterative_char_search());
+
     #[test_case(EXAMPLE_WORDLIST_EMPTY, vec![])]
     #[test_case(EXAMPLE_WORDLIST_1, vec![&ExampleWords1::Get, &ExampleWords1::Give, &ExampleWords1::Go])]
     #[test_case(EXAMPLE_WORDLIST_2, vec![&ExampleWords2::Arm, &ExampleWords2::Army, &ExampleWords2::Man])]
@@ -914,4 +974,117 @@ mod test {
     {
         assert_eq!(root.words().collect::<Vec<_>>(), expected_words);
     }
+
+    #[test_case(EXAMPLE_WORDLIST_EMPTY)]
+    #[test_case(EXAMPLE_WORDLIST_1)]
+    #[test_case(EXAMPLE_WORDLIST_2)]
+    #[test_case(EXAMPLE_WORDLIST_3)]
+    #[test_case(EXAMPLE_WORDLIST_4)]
+    #[test_case(EXAMPLE_WORDLIST_5)]
+    #[test_case(EXAMPLE_WORDLIST_6)]
+    #[test_case(EXAMPLE_WORDLIST_7)]
+    fn test_positive_get_by_index_matches_words<W>(root: WordCharTreeRootNode<W>)
+    where
+        W: std::fmt::Debug + std::cmp::PartialEq,
+    {
+        let words = root.words().collect::<Vec<_>>();
+        assert_eq!(root.len(), words.len());
+        for (idx, word) in words.into_iter().enumerate() {
+            assert_eq!(root.get_by_index(idx), Some(word));
+        }
+        assert_eq!(root.get_by_index(root.len()), None);
+    }
+
+    #[test_case(EXAMPLE_WORDLIST_EMPTY)]
+    #[test_case(EXAMPLE_WORDLIST_1)]
+    #[test_case(EXAMPLE_WORDLIST_2)]
+    #[test_case(EXAMPLE_WORDLIST_3)]
+    #[test_case(EXAMPLE_WORDLIST_4)]
+    #[test_case(EXAMPLE_WORDLIST_5)]
+    #[test_case(EXAMPLE_WORDLIST_6)]
+    #[test_case(EXAMPLE_WORDLIST_7)]
+    fn test_positive_words_rev_matches_words<W>(root: WordCharTreeRootNode<W>)
+    where
+        W: std::fmt::Debug + std::cmp::PartialEq,
+    {
+        let mut words = root.words().collect::<Vec<_>>();
+        assert_eq!(root.words().len(), words.len());
+        assert_eq!(root.words().size_hint(), (words.len(), Some(words.len())));
+        words.reverse();
+        assert_eq!(root.words().rev().collect::<Vec<_>>(), words);
+    }
+
+    #[test_case("ant", Some(0))]
+    #[test_case("Mango", Some(6))]
+    #[test_case("ma", None)]
+    #[test_case("xray", Some(10))]
+    #[test_case("zoo", Some(15))]
+    #[test_case("zoos", None)]
+    #[test_case("", None)]
+    fn test_get_index(word: &str, expected_idx: Option<usize>) {
+        assert_eq!(EXAMPLE_WORDLIST_7.get_index(word), expected_idx);
+        assert_eq!(
+            EXAMPLE_WORDLIST_7.get(word),
+            expected_idx.and_then(|idx| EXAMPLE_WORDLIST_7.get_by_index(idx))
+        );
+    }
+
+    #[test_case("", Some(0..=15))]
+    #[test_case("m", Some(3..=8))]
+    #[test_case("Man", Some(4..=6))]
+    #[test_case("xr", Some(9..=10))]
+    #[test_case("zoo", Some(15..=15))]
+    #[test_case("q", None)]
+    fn test_prefix_range(prefix: &str, expected_range: Option<RangeInclusive<usize>>) {
+        assert_eq!(EXAMPLE_WORDLIST_7.prefix_range(prefix), expected_range);
+        let len = expected_range.map_or(0, |range| range.count());
+        assert_eq!(EXAMPLE_WORDLIST_7.words_with_prefix(prefix).len(), len);
+    }
+
+    #[test]
+    fn test_positive_words_from_both_ends() {
+        let mut words = EXAMPLE_WORDLIST_7.words();
+        assert_eq!(words.next(), Some(&ExampleWords7::Ant));
+        assert_eq!(words.next_back(), Some(&ExampleWords7::Zoo));
+        assert_eq!(words.len(), 14);
+        let mut mane = EXAMPLE_WORDLIST_7.words_with_prefix("man");
+        assert_eq!(mane.len(), 3);
+        assert_eq!(mane.next_back(), Some(&ExampleWords7::Mango));
+        assert_eq!(mane.next(), Some(&ExampleWords7::Man));
+        assert_eq!(mane.next_back(), Some(&ExampleWords7::Mane));
+        assert_eq!(mane.next(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_positive_cursor_path() {
+        let mut cursor = EXAMPLE_WORDLIST_7.cursor();
+        for c in "mang".chars() {
+            assert!(cursor.descend(c));
+        }
+        assert_eq!(cursor.path().collect::<String>(), "mang");
+        assert_eq!(cursor.word(), None);
+        let (c, idx_range, node) = cursor.children().next().unwrap();
+        assert_eq!((*c, idx_range), ('o', 6..=6));
+        assert_eq!(node.word(), Some(&ExampleWords7::Mango));
+        assert_eq!(node.depth(), 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_positive_words_by_len() {
+        let bfs = EXAMPLE_WORDLIST_7.words_bfs().collect::<Vec<_>>();
+        assert_eq!(bfs.len(), EXAMPLE_WORDLIST_7.len());
+        assert_eq!(bfs[..2], [&ExampleWords7::I, &ExampleWords7::Ant]);
+        assert_eq!(bfs.last(), Some(&&ExampleWords7::Zombie));
+        assert_eq!(
+            EXAMPLE_WORDLIST_7.words_of_len(3).collect::<Vec<_>>(),
+            [
+                &ExampleWords7::Ant,
+                &ExampleWords7::Art,
+                &ExampleWords7::Man,
+                &ExampleWords7::Zoo
+            ]
+        );
+    }
 }
@@ -0,0 +1,568 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use super::{
+    CaseFolding, Edges, Normalization, WordCharTreeEdge, WordCharTreeNode, WordCharTreeRootNode,
+    WordCharTreeView, WordTree,
+};
+
+/// Magic bytes at the start of a binary word char tree
+pub const BINARY_TREE_MAGIC: [u8; 4] = *b"WLCT";
+
+/// Version of the binary word char tree format written by [`WordCharTreeRootNode::write_to`]
+pub const BINARY_TREE_VERSION: u16 = 1;
+
+pub(super) const NO_WORD: u32 = u32::MAX;
+pub(super) const HEADER_LEN: usize = 4 + 2 + 2 + 4 + 4 + 4;
+pub(super) const NODE_LEN: usize = 4 + 4 + 4;
+pub(super) const EDGE_LEN: usize = 4 + 4 + 4 + 4;
+pub(super) const CHECKSUM_LEN: usize = 4;
+
+/// Get the code under which a [`CaseFolding`] policy is stored in the header
+fn case_folding_code(case_folding: CaseFolding) -> u8 {
+    match case_folding {
+        CaseFolding::Lowercase => 0,
+        CaseFolding::Full => 1,
+        CaseFolding::Ascii => 2,
+    }
+}
+
+/// Get the [`CaseFolding`] policy for a code from the header
+pub(super) fn case_folding_from_code(code: u8) -> Option<CaseFolding> {
+    match code {
+        0 => Some(CaseFolding::Lowercase),
+        1 => Some(CaseFolding::Full),
+        2 => Some(CaseFolding::Ascii),
+        _ => None,
+    }
+}
+
+/// Get the code under which a [`Normalization`] is stored in the header
+fn normalization_code(normalization: Normalization) -> u8 {
+    match normalization {
+        Normalization::None => 0,
+        #[cfg(feature = "normalization")]
+        Normalization::Nfc => 1,
+        #[cfg(feature = "normalization")]
+        Normalization::Nfkd => 2,
+        #[cfg(feature = "normalization")]
+        Normalization::StripAccents => 3,
+    }
+}
+
+/// Get the [`Normalization`] for a code from the header
+///
+/// Codes of normalization forms that require the `normalization` feature
+/// are only known when the feature is enabled.
+pub(super) fn normalization_from_code(code: u8) -> Option<Normalization> {
+    match code {
+        0 => Some(Normalization::None),
+        #[cfg(feature = "normalization")]
+        1 => Some(Normalization::Nfc),
+        #[cfg(feature = "normalization")]
+        2 => Some(Normalization::Nfkd),
+        #[cfg(feature = "normalization")]
+        3 => Some(Normalization::StripAccents),
+        _ => None,
+    }
+}
+
+/// A word `W` that can be stored in the word table of a binary word char tree
+pub trait BinaryWord: Sized {
+    /// Append the encoded word to `buf`
+    fn encode_word(&self, buf: &mut Vec<u8>);
+    /// Decode a word that was encoded with [`Self::encode_word`]
+    ///
+    /// Returns `None` if the bytes do not hold a valid word.
+    fn decode_word(bytes: &[u8]) -> Option<Self>;
+}
+
+impl BinaryWord for () {
+    fn encode_word(&self, _buf: &mut Vec<u8>) {}
+    fn decode_word(bytes: &[u8]) -> Option<Self> {
+        bytes.is_empty().then_some(())
+    }
+}
+
+impl BinaryWord for String {
+    fn encode_word(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_bytes());
+    }
+    fn decode_word(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+macro_rules! impl_binary_word_for_uint {
+    ($($ty:ty),*) => {$(
+        impl BinaryWord for $ty {
+            fn encode_word(&self, buf: &mut Vec<u8>) {
+                buf.extend_from_slice(&self.to_le_bytes());
+            }
+            fn decode_word(bytes: &[u8]) -> Option<Self> {
+                bytes.try_into().ok().map(<$ty>::from_le_bytes)
+            }
+        }
+    )*};
+}
+
+impl_binary_word_for_uint!(u8, u16, u32, u64);
+
+/// Error returned when reading a binary word char tree fails
+#[derive(Debug)]
+pub enum ReadTreeError {
+    /// Reading from the underlying reader failed
+    Io(io::Error),
+    /// The input ended before the end of the tree
+    Truncated,
+    /// The input does not start with [`BINARY_TREE_MAGIC`]
+    BadMagic,
+    /// The input was written in a version of the format that is not supported
+    UnsupportedVersion(u16),
+    /// The header holds an unknown [`CaseFolding`] policy
+    UnsupportedCaseFolding(u8),
+    /// The header holds an unknown [`Normalization`], or one whose cargo feature is not enabled
+    UnsupportedNormalization(u8),
+    /// The checksum stored in the input does not match the checksum of the input
+    ChecksumMismatch { stored: u32, computed: u32 },
+    /// There are more bytes after the end of the word table than the checksum
+    TrailingBytes,
+    /// The tables of the header do not fit together
+    InvalidHeader,
+    /// An edge holds a value that is not a [`char`]
+    InvalidChar { edge: usize, value: u32 },
+    /// The edges of a node are out of breadth-first order, or not sorted by char
+    InvalidEdges { node: usize },
+    /// An edge points to a node other than the next node in breadth-first order
+    InvalidNodeRef { edge: usize, node: u32 },
+    /// A node refers to a word other than the first word of the `idx_range` of its edge
+    InvalidWordRef { node: usize, word: u32 },
+    /// The word offsets of the word table are not in ascending order
+    InvalidWordOffsets,
+    /// A word in the word table could not be decoded
+    InvalidWord { word: usize },
+    /// The `idx_range` of an edge does not match the words in the subtree of the edge
+    InconsistentIndexRange { edge: usize },
+    /// An edge is not one of the edges of any node
+    UnreachableEdge { edge: usize },
+    /// A node is not the child node of any edge
+    UnreachableNode { node: usize },
+}
+
+impl fmt::Display for ReadTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read tree: {err}"),
+            Self::Truncated => write!(f, "unexpected end of input"),
+            Self::BadMagic => write!(f, "input is not a binary word char tree"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported format version {version}"),
+            Self::UnsupportedCaseFolding(code) => write!(f, "unsupported case folding {code}"),
+            Self::UnsupportedNormalization(code) => write!(f, "unsupported normalization {code}"),
+            Self::ChecksumMismatch { stored, computed } => write!(
+                f,
+                "checksum mismatch (stored {stored:#010x}, computed {computed:#010x})"
+            ),
+            Self::TrailingBytes => write!(f, "trailing bytes after word table"),
+            Self::InvalidHeader => write!(f, "invalid header"),
+            Self::InvalidChar { edge, value } => {
+                write!(f, "edge {edge} holds invalid char {value:#x}")
+            }
+            Self::InvalidEdges { node } => write!(f, "node {node} has invalid edges"),
+            Self::InvalidNodeRef { edge, node } => {
+                write!(f, "edge {edge} has invalid child node {node}")
+            }
+            Self::InvalidWordRef { node, word } => {
+                write!(f, "node {node} has invalid word reference {word}")
+            }
+            Self::InvalidWordOffsets => write!(f, "word table offsets are not ascending"),
+            Self::InvalidWord { word } => write!(f, "word {word} could not be decoded"),
+            Self::InconsistentIndexRange { edge } => {
+                write!(f, "edge {edge} has an inconsistent idx_range")
+            }
+            Self::UnreachableEdge { edge } => write!(f, "edge {edge} is not reachable"),
+            Self::UnreachableNode { node } => write!(f, "node {node} is not reachable"),
+        }
+    }
+}
+
+impl std::error::Error for ReadTreeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadTreeError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl<W: BinaryWord> WordCharTreeRootNode<'_, W> {
+    /// Write the tree in the binary word char tree format
+    ///
+    /// All integers are little-endian. The format consists of:
+    /// - A header: [`BINARY_TREE_MAGIC`], the format version as `u16`, the [`CaseFolding`]
+    ///   policy as `u8` (`0` for lowercase, `1` for full and `2` for ASCII), the [`Normalization`]
+    ///   as `u8` (`0` for none, `1` for NFC, `2` for NFKD and `3` for stripped accents),
+    ///   and the number of nodes, edges and words, each as `u32`.
+    /// - The node table, in breadth-first order starting with the root node. Each node holds
+    ///   the index of its word in the word table (or `u32::MAX` for no word),
+    ///   the index of its first edge in the edge table, and its number of edges.
+    /// - The edge table, in the same breadth-first order, so that the child node of edge `i`
+    ///   is node `i + 1`. Each edge holds its `char_lowercase`, the start and end of its
+    ///   `idx_range`, and the index of its child node in the node table.
+    /// - The word table, in the order of [`Self::words`]. It consists of one more offset
+    ///   than there are words, each as `u32`, followed by the word data. The bytes from
+    ///   [`BinaryWord::encode_word`] for word `i` are found between offset `i` and `i + 1`
+    ///   of the word data.
+    /// - A CRC-32 checksum of all the preceding bytes, as `u32`.
+    pub fn write_to<Wr: Write>(&self, mut writer: Wr) -> io::Result<()> {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+        let mut queue = VecDeque::from([(None, &*self.tree.edges)]);
+        while let Some((word_idx, node_edges)) = queue.pop_front() {
+            nodes.push((word_idx, edges.len(), node_edges.len()));
+            for edge in node_edges {
+                let child_node = nodes.len() + queue.len();
+                edges.push((edge, child_node));
+                let word_idx = edge
+                    .child_node
+                    .word
+                    .as_ref()
+                    .map(|_| *edge.idx_range.start());
+                queue.push_back((word_idx, &*edge.child_node.edges));
+            }
+        }
+
+        let mut buf = Vec::with_capacity(HEADER_LEN + NODE_LEN * nodes.len());
+        buf.extend_from_slice(&BINARY_TREE_MAGIC);
+        buf.extend_from_slice(&BINARY_TREE_VERSION.to_le_bytes());
+        buf.push(case_folding_code(self.case_folding));
+        buf.push(normalization_code(self.normalization));
+        put_u32(&mut buf, nodes.len())?;
+        put_u32(&mut buf, edges.len())?;
+        put_u32(&mut buf, self.len())?;
+        for (word_idx, first_edge, num_edges) in nodes {
+            match word_idx {
+                Some(word_idx) => put_u32(&mut buf, word_idx)?,
+                None => buf.extend_from_slice(&NO_WORD.to_le_bytes()),
+            }
+            put_u32(&mut buf, first_edge)?;
+            put_u32(&mut buf, num_edges)?;
+        }
+        for (edge, child_node) in edges {
+            buf.extend_from_slice(&u32::from(edge.label).to_le_bytes());
+            put_u32(&mut buf, *edge.idx_range.start())?;
+            put_u32(&mut buf, *edge.idx_range.end())?;
+            put_u32(&mut buf, child_node)?;
+        }
+        let mut word_data = vec![];
+        put_u32(&mut buf, 0)?;
+        for word in self.words() {
+            word.encode_word(&mut word_data);
+            put_u32(&mut buf, word_data.len())?;
+        }
+        buf.extend_from_slice(&word_data);
+        let checksum = crc32(&buf);
+        buf.extend_from_slice(&checksum.to_le_bytes());
+        writer.write_all(&buf)
+    }
+}
+
+impl<W: BinaryWord> WordCharTreeRootNode<'_, W> {
+    /// Read a tree that was written with [`Self::write_to`]
+    ///
+    /// The whole input is validated, and any corruption is reported as a [`ReadTreeError`].
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, ReadTreeError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        let view = WordCharTreeView::new(&bytes)?;
+        let mut words = view
+            .words()
+            .enumerate()
+            .map(|(word, bytes)| {
+                W::decode_word(bytes)
+                    .map(Some)
+                    .ok_or(ReadTreeError::InvalidWord { word })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            tree: WordTree {
+                edges: build_edges(&view, 0, &mut words),
+            },
+            case_folding: view.case_folding(),
+            normalization: view.normalization(),
+        })
+    }
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: usize) -> io::Result<()> {
+    let value = u32::try_from(value).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "tree is too large for the binary word char tree format",
+        )
+    })?;
+    buf.extend_from_slice(&value.to_le_bytes());
+    Ok(())
+}
+
+fn build_edges<'a, W>(
+    view: &WordCharTreeView<'_>,
+    node: usize,
+    words: &mut [Option<W>],
+) -> Edges<'a, WordCharTreeEdge<'a, W>> {
+    let raw_node = view.node(node);
+    if raw_node.num_edges == 0 {
+        return Edges::default();
+    }
+    let edges = (raw_node.first_edge..raw_node.first_edge + raw_node.num_edges)
+        .map(|edge| {
+            let raw_edge = view.edge(edge);
+            let child = raw_edge.child_node as usize;
+            let word = view.node(child).word;
+            WordCharTreeEdge {
+                label: char::from_u32(raw_edge.char_lowercase)
+                    .expect("chars are validated by WordCharTreeView::new"),
+                idx_range: raw_edge.idx_start as usize..=raw_edge.idx_end as usize,
+                child_node: WordCharTreeNode {
+                    word: (word != NO_WORD)
+                        .then(|| words[word as usize].take())
+                        .flatten(),
+                    edges: build_edges(view, child, words),
+                },
+            }
+        })
+        .collect();
+    Edges::Owned(edges)
+}
+
+/// CRC-32 (IEEE 802.3) of the given bytes
+pub(super) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::super::WordCharTreeBuilder;
+    use super::*;
+    use test_case::test_case;
+
+    macro_rules! impl_binary_word_for_example_words {
+        ($($ty:ident: [$($variant:ident),*];)*) => {$(
+            impl BinaryWord for $ty {
+                fn encode_word(&self, buf: &mut Vec<u8>) {
+                    let variants = [$($ty::$variant),*];
+                    buf.push(variants.iter().position(|v| v == self).unwrap() as u8);
+                }
+                fn decode_word(bytes: &[u8]) -> Option<Self> {
+                    let variants = [$($ty::$variant),*];
+                    match bytes {
+                        [i] => variants.into_iter().nth(*i as usize),
+                        _ => None,
+                    }
+                }
+            }
+        )*};
+    }
+
+    impl_binary_word_for_example_words! {
+        ExampleWords1: [Get, Give, Go];
+        ExampleWords2: [Arm, Army, Man];
+        ExampleWords3: [A];
+        ExampleWords4: [An];
+        ExampleWords5: [Ant];
+        ExampleWords6: [A, An, Ant];
+        ExampleWords7: [
+            Ant, Art, I, Main, Man, Mane, Mango, Mare, More, XRAM, XRay, Zebra, Zero, Zinc,
+            Zombie, Zoo
+        ];
+    }
+
+    fn to_bytes<W: BinaryWord>(root: &WordCharTreeRootNode<W>) -> Vec<u8> {
+        let mut bytes = vec![];
+        root.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test_case(EXAMPLE_WORDLIST_EMPTY)]
+    #[test_case(EXAMPLE_WORDLIST_1)]
+    #[test_case(EXAMPLE_WORDLIST_2)]
+    #[test_case(EXAMPLE_WORDLIST_3)]
+    #[test_case(EXAMPLE_WORDLIST_4)]
+    #[test_case(EXAMPLE_WORDLIST_5)]
+    #[test_case(EXAMPLE_WORDLIST_6)]
+    #[test_case(EXAMPLE_WORDLIST_7)]
+    fn test_positive_binary_round_trip<W>(root: WordCharTreeRootNode<W>)
+    where
+        W: BinaryWord + std::fmt::Debug + PartialEq,
+    {
+        let bytes = to_bytes(&root);
+        let read = WordCharTreeRootNode::<W>::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(
+            read.words().collect::<Vec<_>>(),
+            root.words().collect::<Vec<_>>()
+        );
+        assert_eq!(read.node_count(), root.node_count());
+        assert_eq!(read.get_max_depth(), root.get_max_depth());
+        assert_eq!(
+            read.is_suitable_for_iterative_char_search(),
+            root.is_suitable_for_iterative_char_search()
+        );
+        assert_eq!(to_bytes(&read), bytes);
+    }
+
+    #[test]
+    fn test_binary_round_trip_string_words() {
+        let root: WordCharTreeRootNode<String> = ["zoo", "zebra", "ant"]
+            .into_iter()
+            .map(|s| (s, s.to_uppercase()))
+            .collect();
+        let read = WordCharTreeRootNode::<String>::read_from(to_bytes(&root).as_slice()).unwrap();
+        assert_eq!(read.get("zebra"), Some(&"ZEBRA".to_string()));
+        assert_eq!(read.get_by_index(0), Some(&"ANT".to_string()));
+    }
+
+    #[test]
+    fn test_binary_round_trip_case_folding() {
+        let mut builder = WordCharTreeBuilder::new().with_case_folding(CaseFolding::Ascii);
+        builder.insert("Crème", 0u8);
+        let bytes = to_bytes(&builder.build());
+        assert_eq!(
+            WordCharTreeView::new(&bytes).unwrap().case_folding(),
+            CaseFolding::Ascii
+        );
+        let read = WordCharTreeRootNode::<u8>::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(read.case_folding(), CaseFolding::Ascii);
+        assert_eq!(read.get("CRèME"), Some(&0));
+        assert_eq!(read.get("CRÈME"), None);
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn test_binary_round_trip_normalization() {
+        let mut builder =
+            WordCharTreeBuilder::new().with_normalization(Normalization::StripAccents);
+        builder.insert("crème", 0u8);
+        let bytes = to_bytes(&builder.build());
+        let view = WordCharTreeView::new(&bytes).unwrap();
+        assert_eq!(view.normalization(), Normalization::StripAccents);
+        assert_eq!(view.get_index("Creme"), Some(0));
+        let read = WordCharTreeRootNode::<u8>::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(read.normalization(), Normalization::StripAccents);
+        assert_eq!(read.get("creme"), Some(&0));
+    }
+
+    fn read_err(bytes: &[u8]) -> ReadTreeError {
+        match WordCharTreeRootNode::<ExampleWords7>::read_from(bytes) {
+            Ok(_) => panic!("corrupt input was accepted"),
+            Err(err) => err,
+        }
+    }
+
+    /// Overwrite bytes at `pos` and fix up the checksum, to get past the checksum verification
+    fn patch(bytes: &[u8], pos: usize, patch: &[u8]) -> Vec<u8> {
+        let mut bytes = bytes[..bytes.len() - CHECKSUM_LEN].to_vec();
+        bytes[pos..pos + patch.len()].copy_from_slice(patch);
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_negative_read_corrupt_input() {
+        let bytes = to_bytes(&EXAMPLE_WORDLIST_7);
+        let edges_start = HEADER_LEN + NODE_LEN * EXAMPLE_WORDLIST_7.node_count();
+
+        assert!(matches!(read_err(&[]), ReadTreeError::Truncated));
+        assert!(matches!(read_err(b"WLCX\x01\x00"), ReadTreeError::BadMagic));
+        assert!(matches!(
+            read_err(&patch(&bytes, 4, &2u16.to_le_bytes())),
+            ReadTreeError::UnsupportedVersion(2)
+        ));
+        assert!(matches!(
+            read_err(&patch(&bytes, 6, &[3])),
+            ReadTreeError::UnsupportedCaseFolding(3)
+        ));
+        assert!(matches!(
+            read_err(&patch(&bytes, 7, &[4])),
+            ReadTreeError::UnsupportedNormalization(4)
+        ));
+        assert!(matches!(
+            read_err(&bytes[..bytes.len() - 1]),
+            ReadTreeError::ChecksumMismatch { .. }
+        ));
+
+        let mut flipped = bytes.clone();
+        flipped[HEADER_LEN + 1] ^= 0x40;
+        assert!(matches!(
+            read_err(&flipped),
+            ReadTreeError::ChecksumMismatch { .. }
+        ));
+
+        assert!(matches!(
+            read_err(&patch(&bytes, edges_start, &0xD800u32.to_le_bytes())),
+            ReadTreeError::InvalidChar { edge: 0, .. }
+        ));
+        assert!(matches!(
+            read_err(&patch(&bytes, edges_start, &u32::from('z').to_le_bytes())),
+            ReadTreeError::InvalidEdges { node: 0 }
+        ));
+        assert!(matches!(
+            read_err(&patch(&bytes, edges_start + 12, &0u32.to_le_bytes())),
+            ReadTreeError::InvalidNodeRef { edge: 0, node: 0 }
+        ));
+        assert!(matches!(
+            read_err(&patch(&bytes, edges_start + 8, &5u32.to_le_bytes())),
+            ReadTreeError::InconsistentIndexRange { .. }
+        ));
+        let last_word = bytes.len() - CHECKSUM_LEN - 1;
+        assert!(matches!(
+            read_err(&patch(&bytes, last_word, &[200])),
+            ReadTreeError::InvalidWord { word: 15 }
+        ));
+    }
+
+    #[test]
+    fn test_negative_read_orphan_edge() {
+        // Append an edge that no node lists, with a child node of its own,
+        // where both records are consistent in themselves.
+        let bytes = to_bytes(&EXAMPLE_WORDLIST_7);
+        let num_nodes = EXAMPLE_WORDLIST_7.node_count();
+        let num_edges = num_nodes - 1;
+        let edges_start = HEADER_LEN + NODE_LEN * num_nodes;
+        let edges_end = edges_start + EDGE_LEN * num_edges;
+        let mut orphan = bytes[..8].to_vec();
+        for count in [num_nodes + 1, num_edges + 1] {
+            orphan.extend_from_slice(&(count as u32).to_le_bytes());
+        }
+        orphan.extend_from_slice(&bytes[16..edges_start]);
+        for value in [0, num_edges as u32, 0] {
+            orphan.extend_from_slice(&value.to_le_bytes());
+        }
+        orphan.extend_from_slice(&bytes[edges_start..edges_end]);
+        for value in [u32::from('q'), 0, 0, num_nodes as u32] {
+            orphan.extend_from_slice(&value.to_le_bytes());
+        }
+        orphan.extend_from_slice(&bytes[edges_end..bytes.len() - CHECKSUM_LEN]);
+        let checksum = crc32(&orphan);
+        orphan.extend_from_slice(&checksum.to_le_bytes());
+
+        assert!(matches!(
+            read_err(&orphan),
+            ReadTreeError::UnreachableNode { node } if node == num_nodes
+        ));
+    }
+}
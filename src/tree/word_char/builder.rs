@@ -0,0 +1,194 @@
+use super::normalization::edge_chars;
+use super::{CaseFolding, Normalization, WordCharTreeRootNode, WordTreeBuilder};
+
+/// Builds an owned [`WordCharTreeRootNode`] from words inserted in any order
+///
+/// The [`char`]s of an inserted string are mapped by the [`CaseFolding`] policy and the
+/// [`Normalization`] of the builder, which are recorded on the built tree, so that lookups
+/// in the tree map strings the same way.
+/// The edges of every node are sorted by char, and the `idx_range`s of the edges are
+/// computed when the tree is built, so that the index of each word is its position
+/// in the order of [`WordCharTreeRootNode::words`].
+pub struct WordCharTreeBuilder<W> {
+    tree: WordTreeBuilder<char, W>,
+    case_folding: CaseFolding,
+    normalization: Normalization,
+}
+
+impl<W> Default for WordCharTreeBuilder<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W> WordCharTreeBuilder<W> {
+    /// Create a builder for an empty tree,
+    /// with the default [`CaseFolding`] policy and no [`Normalization`]
+    pub fn new() -> Self {
+        Self {
+            tree: WordTreeBuilder::new(),
+            case_folding: CaseFolding::default(),
+            normalization: Normalization::default(),
+        }
+    }
+    /// Set the [`CaseFolding`] policy of the tree
+    ///
+    /// Must be set before any words are inserted, so that all words are mapped the same way.
+    pub fn with_case_folding(mut self, case_folding: CaseFolding) -> Self {
+        assert!(self.is_empty(), "case folding set after inserting words");
+        self.case_folding = case_folding;
+        self
+    }
+    /// Set the [`Normalization`] of the tree
+    ///
+    /// Must be set before any words are inserted, so that all words are mapped the same way.
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        assert!(self.is_empty(), "normalization set after inserting words");
+        self.normalization = normalization;
+        self
+    }
+    /// Insert a word `W` under the given string
+    ///
+    /// Returns the word that was previously inserted under the same string, if any.
+    /// Empty strings cannot be represented in the tree and are ignored, in which case
+    /// the word is handed back.
+    pub fn insert(&mut self, s: &str, word: W) -> Option<W> {
+        self.tree
+            .insert(edge_chars(s, self.case_folding, self.normalization), word)
+    }
+    /// Get the number of words inserted so far
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+    /// Returns `true` if no words have been inserted yet
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+    /// Build the tree
+    pub fn build<'a>(self) -> WordCharTreeRootNode<'a, W> {
+        WordCharTreeRootNode {
+            tree: self.tree.build(),
+            case_folding: self.case_folding,
+            normalization: self.normalization,
+        }
+    }
+}
+
+impl<S: AsRef<str>, W> FromIterator<(S, W)> for WordCharTreeRootNode<'_, W> {
+    fn from_iter<I: IntoIterator<Item = (S, W)>>(iter: I) -> Self {
+        let mut builder = WordCharTreeBuilder::new();
+        for (s, word) in iter {
+            builder.insert(s.as_ref(), word);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::super::{WordCharDawg, WordRadixTreeRootNode};
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_positive_builder_matches_const_tree() {
+        let strings = [
+            "zoo", "XRAM", "xray", "main", "man", "mane", "mango", "mare", "more", "ant", "art",
+            "i", "zebra", "zero", "zinc", "zombie",
+        ];
+        let root: WordCharTreeRootNode<_> = strings
+            .iter()
+            .map(|s| (s, EXAMPLE_WORDLIST_7.get(s).unwrap().clone()))
+            .collect();
+        assert_eq!(
+            root.words().collect::<Vec<_>>(),
+            EXAMPLE_WORDLIST_7.words().collect::<Vec<_>>()
+        );
+        assert_eq!(root.node_count(), EXAMPLE_WORDLIST_7.node_count());
+        assert_eq!(root.get_max_depth(), EXAMPLE_WORDLIST_7.get_max_depth());
+        for s in strings {
+            assert_eq!(root.get_index(s), EXAMPLE_WORDLIST_7.get_index(s));
+            for end in 1..=s.len() {
+                let prefix = &s[..end];
+                assert_eq!(
+                    root.prefix_range(prefix),
+                    EXAMPLE_WORDLIST_7.prefix_range(prefix)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_builder_insert_replaces_and_ignores_empty() {
+        let mut builder = WordCharTreeBuilder::new();
+        assert_eq!(builder.insert("arm", 1), None);
+        assert_eq!(builder.insert("ARM", 2), Some(1));
+        assert_eq!(builder.insert("", 3), Some(3));
+        assert_eq!(builder.len(), 1);
+        let root = builder.build();
+        assert_eq!(root.get("arm"), Some(&2));
+        assert!(root.is_fully_well_formed());
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test_case(Normalization::None, "creme", None)]
+    #[test_case(Normalization::Nfc, "cre\u{300}me", Some(1))]
+    #[test_case(Normalization::Nfc, "creme", None)]
+    #[test_case(Normalization::Nfkd, "CRE\u{300}ME", Some(1))]
+    #[test_case(Normalization::StripAccents, "creme", Some(1))]
+    #[test_case(Normalization::StripAccents, "CRÉME", Some(1))]
+    #[test_case(Normalization::StripAccents, "ano", Some(0))]
+    fn test_builder_normalization(normalization: Normalization, s: &str, expected: Option<usize>) {
+        let mut builder = WordCharTreeBuilder::new().with_normalization(normalization);
+        builder.insert("crème", ());
+        builder.insert("año", ());
+        let root = builder.build();
+        assert_eq!(root.normalization(), normalization);
+        assert_eq!(root.get_index(s), expected);
+        assert_eq!(root.prefix_range(s), expected.map(|idx| idx..=idx));
+        assert_eq!(WordRadixTreeRootNode::from(&root).get_index(s), expected);
+        assert_eq!(WordCharDawg::from(&root).get_index(s), expected);
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test_case(CaseFolding::Ascii, "CRÈME", Some(0))]
+    #[test_case(CaseFolding::Ascii, "ØRESUND", Some(1))]
+    #[test_case(CaseFolding::Lowercase, "CRÈME", Some(0) ; "lowercase creme")]
+    #[test_case(CaseFolding::Full, "ØRESUND", Some(1) ; "full oresund")]
+    fn test_builder_uppercase_accented_word(
+        case_folding: CaseFolding,
+        s: &str,
+        expected: Option<usize>,
+    ) {
+        let mut builder = WordCharTreeBuilder::new()
+            .with_case_folding(case_folding)
+            .with_normalization(Normalization::StripAccents);
+        builder.insert("creme", ());
+        builder.insert("oresund", ());
+        let root = builder.build();
+        assert_eq!(root.get_index(s), expected);
+        assert_eq!(WordRadixTreeRootNode::from(&root).get_index(s), expected);
+        assert_eq!(WordCharDawg::from(&root).get_index(s), expected);
+    }
+
+    #[test_case(CaseFolding::Lowercase, "STRASSE", None)]
+    #[test_case(CaseFolding::Lowercase, "STRAßE", Some(0))]
+    #[test_case(CaseFolding::Full, "STRASSE", Some(0))]
+    #[test_case(CaseFolding::Full, "strasse", Some(0) ; "full lowercase strasse")]
+    #[test_case(CaseFolding::Ascii, "STRAßE", Some(0))]
+    #[test_case(CaseFolding::Ascii, "école", None)]
+    #[test_case(CaseFolding::Ascii, "ÉCOLE", Some(1) ; "ascii uppercase ecole")]
+    #[test_case(CaseFolding::Ascii, "École", Some(1))]
+    fn test_builder_case_folding(case_folding: CaseFolding, s: &str, expected: Option<usize>) {
+        let mut builder = WordCharTreeBuilder::new().with_case_folding(case_folding);
+        builder.insert("Straße", ());
+        builder.insert("École", ());
+        let root = builder.build();
+        assert_eq!(root.case_folding(), case_folding);
+        assert_eq!(root.get_index(s), expected);
+        let radix = WordRadixTreeRootNode::from(&root);
+        assert_eq!(radix.get_index(s), expected);
+        assert_eq!(WordCharDawg::from(&root).get_index(s), expected);
+    }
+}
@@ -0,0 +1,133 @@
+use core::char::{ToLowercase, ToUppercase};
+use core::iter::{FlatMap, Once};
+use core::str::Chars;
+
+/// How the [`char`]s of strings are mapped to the lowercase `char` edges of a tree
+///
+/// The policy is recorded on the tree when it is built, and the same policy is used
+/// for every lookup in the tree, so that strings are always mapped the same way.
+/// A single char may map to several chars, each of which is one edge in the tree.
+///
+/// No policy applies language-specific mappings, such as those for Turkish and Azeri.
+///
+/// | Char           | [`Lowercase`](`Self::Lowercase`) | [`Full`](`Self::Full`) | [`Ascii`](`Self::Ascii`) |
+/// |----------------|------------|-----------|-------|
+/// | `A`            | `a`        | `a`       | `a`   |
+/// | `É`            | `é`        | `é`       | `É`   |
+/// | `ß`            | `ß`        | `ss`      | `ß`   |
+/// | `ς` (final)    | `ς`        | `σ`       | `ς`   |
+/// | `ﬁ` (ligature) | `ﬁ`        | `fi`      | `ﬁ`   |
+/// | `İ` (dotted)   | `i̇` (`i` followed by U+0307) | `i̇` | `İ` |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CaseFolding {
+    /// Map each char to its lowercase mapping, as with [`char::to_lowercase`]
+    #[default]
+    Lowercase,
+    /// Map each char to the lowercase mapping of its uppercase mapping,
+    /// so that for example `ß`, `SS` and `ss` all match
+    ///
+    /// This approximates full Unicode case folding, as given by `CaseFolding.txt`,
+    /// and agrees with it for most chars, but not for all of them:
+    /// - The capital sharp s `ẞ` maps to `ß` instead of `ss`, so `STRAẞE` and `strasse`
+    ///   do not match.
+    /// - The Cherokee letters map to the small letters instead of the capital letters.
+    ///   Both cases of a letter still map to the same char, so they match each other.
+    Full,
+    /// Map only the ASCII letters `A` to `Z` to `a` to `z`, and leave all other chars as they are
+    Ascii,
+}
+
+impl CaseFolding {
+    /// Returns an iterator over the chars of the string, mapped by this policy
+    pub fn fold(self, s: &str) -> FoldedChars<Chars<'_>> {
+        self.fold_chars(s.chars())
+    }
+    /// Returns an iterator over the chars, mapped by this policy
+    pub fn fold_chars<I: Iterator<Item = char>>(self, chars: I) -> FoldedChars<I> {
+        FoldedChars {
+            chars,
+            case_folding: self,
+            current: None,
+        }
+    }
+    /// Returns the string, mapped by this policy
+    #[cfg(feature = "std")]
+    pub fn fold_str(self, s: &str) -> String {
+        self.fold(s).collect()
+    }
+    fn fold_char(self, c: char) -> FoldedChar {
+        match self {
+            Self::Lowercase => FoldedChar::Lowercase(c.to_lowercase()),
+            Self::Full => FoldedChar::Full(c.to_uppercase().flat_map(char::to_lowercase)),
+            Self::Ascii => FoldedChar::Ascii(core::iter::once(c.to_ascii_lowercase())),
+        }
+    }
+}
+
+/// An iterator over chars, mapped by a [`CaseFolding`] policy
+pub struct FoldedChars<I> {
+    chars: I,
+    case_folding: CaseFolding,
+    current: Option<FoldedChar>,
+}
+
+enum FoldedChar {
+    Lowercase(ToLowercase),
+    Full(FlatMap<ToUppercase, ToLowercase, fn(char) -> ToLowercase>),
+    Ascii(Once<char>),
+}
+
+impl Iterator for FoldedChar {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        match self {
+            Self::Lowercase(chars) => chars.next(),
+            Self::Full(chars) => chars.next(),
+            Self::Ascii(chars) => chars.next(),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for FoldedChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(c);
+            }
+            self.current = Some(self.case_folding.fold_char(self.chars.next()?));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(CaseFolding::Lowercase, "Straße", "straße")]
+    #[test_case(CaseFolding::Full, "Straße", "strasse")]
+    #[test_case(CaseFolding::Ascii, "Straße", "straße")]
+    #[test_case(CaseFolding::Lowercase, "ΣΟΦΟΣ σοφος", "σοφοσ σοφος")]
+    #[test_case(CaseFolding::Full, "ΣΟΦΟΣ σοφος", "σοφοσ σοφοσ")]
+    #[test_case(CaseFolding::Lowercase, "İstanbul", "i\u{307}stanbul")]
+    #[test_case(CaseFolding::Full, "İstanbul", "i\u{307}stanbul")]
+    #[test_case(CaseFolding::Ascii, "İSTANBUL", "İstanbul")]
+    #[test_case(CaseFolding::Full, "ﬁle", "file")]
+    #[test_case(CaseFolding::Ascii, "CRÈME", "crÈme")]
+    fn test_fold(case_folding: CaseFolding, s: &str, expected: &str) {
+        assert_eq!(case_folding.fold_str(s), expected);
+    }
+
+    /// Where the upper-then-lower mapping differs from the mapping in `CaseFolding.txt`
+    #[test_case("ẞ", "ß", "ss"; "capital sharp s")]
+    #[test_case("Ꭰ", "ꭰ", "Ꭰ"; "cherokee capital a")]
+    #[test_case("ꭰ", "ꭰ", "Ꭰ"; "cherokee small a")]
+    fn test_full_differs_from_case_folding_txt(s: &str, expected: &str, case_folding_txt: &str) {
+        let folded = CaseFolding::Full.fold_str(s);
+        assert_eq!(folded, expected);
+        assert_ne!(folded, case_folding_txt);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use super::normalization::edge_chars;
+use super::{CaseFolding, Normalization, WordCharTreeNode, WordCharTreeRootNode};
+use crate::Words;
+
+/// A minimized directed acyclic word graph (DAWG), also known as a DAFSA,
+/// built from a [`WordCharTreeRootNode`]
+///
+/// Nodes whose suffix subtrees are equivalent are merged into a single node.
+/// Because a merged node can be reached along several paths, the edges do not store
+/// absolute `idx_range`s. Instead, each edge stores the number of words that come before
+/// the words of its target node, relative to the words of its source node. The index
+/// of a word is the sum of the offsets of the edges along its path.
+///
+/// The words `W` themselves are stored in a word table, in the order of their index.
+pub struct WordCharDawg<W> {
+    nodes: Vec<DawgNode>,
+    edges: Vec<DawgEdge>,
+    root: usize,
+    words: Vec<W>,
+    source_node_count: usize,
+    case_folding: CaseFolding,
+    normalization: Normalization,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct DawgNode {
+    is_final: bool,
+    first_edge: usize,
+    num_edges: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct DawgEdge {
+    char_lowercase: char,
+    offset: usize,
+    target: usize,
+}
+
+/// The key under which equivalent nodes are registered during minimization
+#[derive(PartialEq, Eq, Hash)]
+struct NodeSignature {
+    is_final: bool,
+    edges: Vec<DawgEdge>,
+}
+
+/// The reduction in size from a word char tree to the corresponding [`WordCharDawg`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DawgCompressionReport {
+    /// Number of nodes in the word char tree, including the root node
+    pub tree_node_count: usize,
+    /// Number of nodes in the DAWG, including the root node
+    pub dawg_node_count: usize,
+}
+
+impl DawgCompressionReport {
+    /// Number of nodes saved by merging equivalent suffix subtrees
+    pub fn nodes_saved(&self) -> usize {
+        self.tree_node_count - self.dawg_node_count
+    }
+    /// Size of the DAWG relative to the word char tree, measured in nodes
+    pub fn ratio(&self) -> f64 {
+        self.dawg_node_count as f64 / self.tree_node_count as f64
+    }
+}
+
+impl<W: Clone> From<&WordCharTreeRootNode<'_, W>> for WordCharDawg<W> {
+    fn from(root: &WordCharTreeRootNode<'_, W>) -> Self {
+        let mut builder = DawgBuilder::default();
+        let mut root_edges = Vec::with_capacity(root.tree.edges.len());
+        for edge in root.tree.edges.iter() {
+            let target = builder.register(&edge.child_node, *edge.idx_range.start());
+            root_edges.push(DawgEdge {
+                char_lowercase: edge.label,
+                offset: *edge.idx_range.start(),
+                target,
+            });
+        }
+        let root_id = builder.insert(false, root_edges);
+        Self {
+            nodes: builder.nodes,
+            edges: builder.edges,
+            root: root_id,
+            words: root.words().cloned().collect(),
+            source_node_count: root.node_count(),
+            case_folding: root.case_folding,
+            normalization: root.normalization,
+        }
+    }
+}
+
+#[derive(Default)]
+struct DawgBuilder {
+    nodes: Vec<DawgNode>,
+    edges: Vec<DawgEdge>,
+    registry: HashMap<NodeSignature, usize>,
+}
+
+impl DawgBuilder {
+    /// Register the subtree of `node`, where `start` is the index of the first word of the subtree
+    fn register<W>(&mut self, node: &WordCharTreeNode<'_, W>, start: usize) -> usize {
+        let mut edges = Vec::with_capacity(node.edges.len());
+        for edge in node.edges.iter() {
+            let edge_start = *edge.idx_range.start();
+            let target = self.register(&edge.child_node, edge_start);
+            edges.push(DawgEdge {
+                char_lowercase: edge.label,
+                offset: edge_start - start,
+                target,
+            });
+        }
+        self.insert(node.word.is_some(), edges)
+    }
+    /// Insert a node unless an equivalent node has already been registered
+    fn insert(&mut self, is_final: bool, edges: Vec<DawgEdge>) -> usize {
+        let signature = NodeSignature { is_final, edges };
+        if let Some(&id) = self.registry.get(&signature) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(DawgNode {
+            is_final,
+            first_edge: self.edges.len(),
+            num_edges: signature.edges.len(),
+        });
+        self.edges.extend(signature.edges.iter().cloned());
+        self.registry.insert(signature, id);
+        id
+    }
+}
+
+impl<W> WordCharDawg<W> {
+    /// Returns an iterator over the words `W` of the DAWG, in the same order as the source tree
+    pub fn words(&self) -> Words<'_, W> {
+        Words::new(Box::new(self.words.iter()))
+    }
+    /// Get the number of words in the DAWG
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+    /// Returns `true` if the DAWG contains no words
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+    /// Get the number of nodes in the DAWG, including the root node
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+    /// Get the number of edges in the DAWG
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+    /// Get the reduction in node count compared to the tree that the DAWG was built from
+    pub fn compression_report(&self) -> DawgCompressionReport {
+        DawgCompressionReport {
+            tree_node_count: self.source_node_count,
+            dawg_node_count: self.node_count(),
+        }
+    }
+    /// Get the case folding policy of the word char tree that the DAWG was built from
+    pub fn case_folding(&self) -> CaseFolding {
+        self.case_folding
+    }
+    /// Get the Unicode normalization of the word char tree that the DAWG was built from
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+    /// Look up the word `W` for the given string
+    ///
+    /// The [`char`]s of the string are mapped by the [case folding](`Self::case_folding`)
+    /// policy and the [normalization](`Self::normalization`) before following the edge
+    /// for each of them.
+    pub fn get(&self, word: &str) -> Option<&W> {
+        self.get_index(word).map(|idx| &self.words[idx])
+    }
+    /// Look up the index of the word for the given string
+    ///
+    /// The index is the sum of the word-count offsets of the edges along the path of the word.
+    pub fn get_index(&self, word: &str) -> Option<usize> {
+        let mut node = &self.nodes[self.root];
+        let mut idx = 0;
+        let mut is_empty = true;
+        for c in edge_chars(word, self.case_folding, self.normalization) {
+            let edge = self
+                .node_edges(node)
+                .iter()
+                .find(|e| e.char_lowercase == c)?;
+            idx += edge.offset;
+            node = &self.nodes[edge.target];
+            is_empty = false;
+        }
+        (node.is_final && !is_empty).then_some(idx)
+    }
+    /// Look up the word `W` at the given index
+    pub fn get_by_index(&self, idx: usize) -> Option<&W> {
+        self.words.get(idx)
+    }
+    fn node_edges(&self, node: &DawgNode) -> &[DawgEdge] {
+        &self.edges[node.first_edge..node.first_edge + node.num_edges]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(EXAMPLE_WORDLIST_EMPTY)]
+    #[test_case(EXAMPLE_WORDLIST_1)]
+    #[test_case(EXAMPLE_WORDLIST_2)]
+    #[test_case(EXAMPLE_WORDLIST_3)]
+    #[test_case(EXAMPLE_WORDLIST_4)]
+    #[test_case(EXAMPLE_WORDLIST_5)]
+    #[test_case(EXAMPLE_WORDLIST_6)]
+    #[test_case(EXAMPLE_WORDLIST_7)]
+    fn test_positive_dawg_words_same_as_tree<W>(root: WordCharTreeRootNode<W>)
+    where
+        W: Clone + std::fmt::Debug + PartialEq,
+    {
+        let dawg = WordCharDawg::from(&root);
+        assert_eq!(dawg.len(), root.len());
+        assert_eq!(
+            dawg.words().collect::<Vec<_>>(),
+            root.words().collect::<Vec<_>>()
+        );
+    }
+
+    #[test_case("ant", Some(0))]
+    #[test_case("Mango", Some(6))]
+    #[test_case("ma", None)]
+    #[test_case("xray", Some(10))]
+    #[test_case("zoo", Some(15))]
+    #[test_case("zoos", None)]
+    #[test_case("", None)]
+    fn test_dawg_get_index(word: &str, expected_idx: Option<usize>) {
+        let dawg = WordCharDawg::from(&EXAMPLE_WORDLIST_7);
+        assert_eq!(dawg.get_index(word), expected_idx);
+        assert_eq!(EXAMPLE_WORDLIST_7.get_index(word), expected_idx);
+        assert_eq!(dawg.get(word), EXAMPLE_WORDLIST_7.get(word));
+    }
+
+    #[test]
+    fn test_dawg_index_to_word_matches_tree() {
+        let dawg = WordCharDawg::from(&EXAMPLE_WORDLIST_7);
+        for idx in 0..dawg.len() {
+            assert_eq!(dawg.get_by_index(idx), EXAMPLE_WORDLIST_7.get_by_index(idx));
+        }
+        assert_eq!(dawg.get_by_index(dawg.len()), None);
+    }
+
+    #[test]
+    fn test_dawg_compression_report() {
+        let dawg = WordCharDawg::from(&EXAMPLE_WORDLIST_7);
+        let report = dawg.compression_report();
+        assert_eq!(report.tree_node_count, EXAMPLE_WORDLIST_7.node_count());
+        assert_eq!(report.dawg_node_count, dawg.node_count());
+        assert!(report.nodes_saved() > 0);
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt;
+
+use super::{WordCharTreeEdge, WordCharTreeRootNode};
+
+/// Number of unchanged words shown around each change when a [`TreeDiff`] is rendered
+const CONTEXT_LINES: usize = 3;
+
+/// Compare the words of two word char trees
+///
+/// The trees are walked side by side along their sorted edges, and each word is identified
+/// by the string of the [`char`]s along its path, as mapped by the case folding policy and
+/// the normalization of its tree. The words `W` and `V` themselves are not compared.
+///
+/// # Panics
+///
+/// Panics if the trees map strings to chars differently,
+/// that is, if they have different case folding policies or normalizations.
+pub fn diff<W, V>(a: &WordCharTreeRootNode<'_, W>, b: &WordCharTreeRootNode<'_, V>) -> TreeDiff {
+    a.assert_same_mapping(b);
+    let mut tree_diff = TreeDiff::default();
+    diff_edges(
+        &a.tree.edges,
+        &b.tree.edges,
+        &mut String::new(),
+        &mut tree_diff,
+    );
+    tree_diff
+}
+
+/// The differences between the words of two word char trees, as returned by [`diff`]
+///
+/// The [`Display`](`fmt::Display`) implementation renders the differences as a unified diff
+/// of the two lists of words, where each changed word is shown with up to three unchanged words
+/// around it. A word whose prefix status changed is followed by a line starting with `\`,
+/// in the way that unified diffs annotate lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreeDiff {
+    /// The words that are only in the new tree, with their index in the new tree
+    pub added: Vec<DiffWord>,
+    /// The words that are only in the old tree, with their index in the old tree
+    pub removed: Vec<DiffWord>,
+    /// The words that are in both trees, but at different indices
+    pub moved: Vec<MovedWord>,
+    /// The words that are in both trees, and are a prefix of another word in only one of them
+    pub prefix_changes: Vec<PrefixStatusChange>,
+    /// All words of both trees in order, for rendering
+    lines: Vec<DiffLine>,
+}
+
+/// A word that was added or removed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffWord {
+    pub word: String,
+    pub index: usize,
+}
+
+/// A word whose index differs between the two trees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovedWord {
+    pub word: String,
+    pub old_index: usize,
+    pub new_index: usize,
+}
+
+/// A word that became, or stopped being, a prefix of another word
+///
+/// A word that is a prefix of another word makes a tree unsuitable for iterative char search
+/// (see [`WordCharTreeRootNode::is_suitable_for_iterative_char_search`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixStatusChange {
+    pub word: String,
+    /// Whether the word is a prefix of another word in the new tree
+    pub is_prefix: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffLine {
+    kind: DiffLineKind,
+    word: String,
+    /// The new prefix status of an unchanged word, if it changed
+    prefix_change: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLineKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+impl TreeDiff {
+    /// Returns `true` if the two trees have the same words, at the same indices,
+    /// with the same prefix status
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.prefix_changes.is_empty()
+    }
+}
+
+fn diff_edges<W, V>(
+    a: &[WordCharTreeEdge<'_, W>],
+    b: &[WordCharTreeEdge<'_, V>],
+    path: &mut String,
+    tree_diff: &mut TreeDiff,
+) {
+    let mut a = a.iter().peekable();
+    let mut b = b.iter().peekable();
+    loop {
+        let (a_edge, b_edge) = match (a.peek(), b.peek()) {
+            (None, None) => break,
+            (Some(a_edge), Some(b_edge)) if a_edge.label == b_edge.label => (a.next(), b.next()),
+            (Some(a_edge), Some(b_edge)) if a_edge.label < b_edge.label => (a.next(), None),
+            (Some(_), None) => (a.next(), None),
+            (_, Some(_)) => (None, b.next()),
+        };
+        let label = a_edge.map_or_else(|| b_edge.unwrap().label, |edge| edge.label);
+        path.push(label);
+        let a_index = a_edge
+            .filter(|edge| edge.child_node.word.is_some())
+            .map(|edge| *edge.idx_range.start());
+        let b_index = b_edge
+            .filter(|edge| edge.child_node.word.is_some())
+            .map(|edge| *edge.idx_range.start());
+        let word = || path.clone();
+        match (a_index, b_index) {
+            (Some(old_index), Some(new_index)) => {
+                if old_index != new_index {
+                    tree_diff.moved.push(MovedWord {
+                        word: word(),
+                        old_index,
+                        new_index,
+                    });
+                }
+                let was_prefix = !a_edge.unwrap().child_node.edges.is_empty();
+                let is_prefix = !b_edge.unwrap().child_node.edges.is_empty();
+                let prefix_change = (was_prefix != is_prefix).then_some(is_prefix);
+                if prefix_change.is_some() {
+                    tree_diff.prefix_changes.push(PrefixStatusChange {
+                        word: word(),
+                        is_prefix,
+                    });
+                }
+                tree_diff.lines.push(DiffLine {
+                    kind: DiffLineKind::Unchanged,
+                    word: word(),
+                    prefix_change,
+                });
+            }
+            (Some(index), None) => {
+                tree_diff.removed.push(DiffWord {
+                    word: word(),
+                    index,
+                });
+                tree_diff.lines.push(DiffLine {
+                    kind: DiffLineKind::Removed,
+                    word: word(),
+                    prefix_change: None,
+                });
+            }
+            (None, Some(index)) => {
+                tree_diff.added.push(DiffWord {
+                    word: word(),
+                    index,
+                });
+                tree_diff.lines.push(DiffLine {
+                    kind: DiffLineKind::Added,
+                    word: word(),
+                    prefix_change: None,
+                });
+            }
+            (None, None) => {}
+        }
+        diff_edges(
+            a_edge.map_or(&[][..], |edge| &edge.child_node.edges[..]),
+            b_edge.map_or(&[][..], |edge| &edge.child_node.edges[..]),
+            path,
+            tree_diff,
+        );
+        path.pop();
+    }
+}
+
+impl fmt::Display for TreeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        writeln!(f, "--- a")?;
+        writeln!(f, "+++ b")?;
+        let is_change =
+            |line: &DiffLine| line.kind != DiffLineKind::Unchanged || line.prefix_change.is_some();
+        let mut pos = 0;
+        while let Some(offset) = self.lines[pos..].iter().position(is_change) {
+            // Extend the hunk for as long as the next change is within reach of its context.
+            let start = (pos + offset).saturating_sub(CONTEXT_LINES);
+            let mut end = pos + offset + 1;
+            while let Some(next) = self.lines[end..].iter().position(is_change) {
+                if next > 2 * CONTEXT_LINES {
+                    break;
+                }
+                end += next + 1;
+            }
+            let end = (end + CONTEXT_LINES).min(self.lines.len());
+            self.fmt_hunk(f, start, end)?;
+            pos = end;
+        }
+        Ok(())
+    }
+}
+
+impl TreeDiff {
+    fn fmt_hunk(&self, f: &mut fmt::Formatter<'_>, start: usize, end: usize) -> fmt::Result {
+        let count = |lines: &[DiffLine], skip: DiffLineKind| {
+            lines.iter().filter(|line| line.kind != skip).count()
+        };
+        let range = |before: usize, len: usize| {
+            // Unified diffs number lines from 1, and an empty range starts at the line before it.
+            let start = if len == 0 { before } else { before + 1 };
+            format!("{start},{len}")
+        };
+        let old = range(
+            count(&self.lines[..start], DiffLineKind::Added),
+            count(&self.lines[start..end], DiffLineKind::Added),
+        );
+        let new = range(
+            count(&self.lines[..start], DiffLineKind::Removed),
+            count(&self.lines[start..end], DiffLineKind::Removed),
+        );
+        writeln!(f, "@@ -{old} +{new} @@")?;
+        for line in &self.lines[start..end] {
+            let marker = match line.kind {
+                DiffLineKind::Unchanged => ' ',
+                DiffLineKind::Added => '+',
+                DiffLineKind::Removed => '-',
+            };
+            writeln!(f, "{marker}{}", line.word)?;
+            match line.prefix_change {
+                Some(true) => writeln!(f, "\\ now a prefix of another word")?,
+                Some(false) => writeln!(f, "\\ no longer a prefix of another word")?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::example_tree;
+    use super::super::{CaseFolding, WordCharTreeBuilder};
+    use super::*;
+
+    #[test]
+    fn test_diff_lists_changes() {
+        let a = example_tree(&["arm", "man", "zoo"]);
+        let b = example_tree(&["arm", "army", "zoo", "zebra"]);
+        let tree_diff = diff(&a, &b);
+        let word = |word: &str, index| DiffWord {
+            word: word.to_string(),
+            index,
+        };
+        assert_eq!(tree_diff.added, [word("army", 1), word("zebra", 2)]);
+        assert_eq!(tree_diff.removed, [word("man", 1)]);
+        assert_eq!(
+            tree_diff.moved,
+            [MovedWord {
+                word: "zoo".to_string(),
+                old_index: 2,
+                new_index: 3
+            }]
+        );
+        assert_eq!(
+            tree_diff.prefix_changes,
+            [PrefixStatusChange {
+                word: "arm".to_string(),
+                is_prefix: true
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_of_equal_trees_is_empty() {
+        let a = example_tree(&["arm", "army", "man"]);
+        let tree_diff = diff(&a, &a);
+        assert!(tree_diff.is_empty());
+        assert_eq!(tree_diff.to_string(), "");
+    }
+
+    #[test]
+    fn test_render_unified_diff() {
+        let a = example_tree(&["arm", "army", "b", "c", "d", "e", "f", "g", "h", "i", "j"]);
+        let b = example_tree(&["arm", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k"]);
+        let expected = "\
+--- a
++++ b
+@@ -1,5 +1,4 @@
+ arm
+\\ no longer a prefix of another word
+-army
+ b
+ c
+ d
+@@ -9,3 +8,4 @@
+ h
+ i
+ j
++k
+";
+        assert_eq!(diff(&a, &b).to_string(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "trees map strings to chars differently")]
+    fn test_negative_diff_different_case_folding() {
+        let full = WordCharTreeBuilder::<()>::new()
+            .with_case_folding(CaseFolding::Full)
+            .build();
+        diff(&example_tree(&["arm"]), &full);
+    }
+}
@@ -0,0 +1,265 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::normalization::edge_chars;
+use super::{CaseFolding, Normalization, WordTree, WordTreeBuilder};
+use crate::WordTreeWords;
+
+/// The root node of a tree, where the edges are extended grapheme clusters
+/// and the nodes are `Option<W>` words
+///
+/// Where a word char tree has one edge per [`char`], a grapheme tree has one edge per
+/// user-perceived character, so that emoji sequences such as `👍🏽` and letters followed by
+/// combining marks such as `e` followed by U+0301 are each a single edge.
+/// Lookups, iteration and depth all count user-perceived characters.
+///
+/// Strings are mapped by the [`CaseFolding`] policy and the [`Normalization`] of the tree,
+/// and then split into grapheme clusters as specified by [UAX #29].
+/// The tree itself is a [`WordTree`] with string labels.
+///
+/// [UAX #29]: https://www.unicode.org/reports/tr29/
+pub struct WordGraphemeTreeRootNode<'a, W> {
+    tree: WordTree<'a, Box<str>, W>,
+    case_folding: CaseFolding,
+    normalization: Normalization,
+}
+
+impl<'a, W> WordGraphemeTreeRootNode<'a, W> {
+    /// Get the underlying [`WordTree`] with grapheme cluster labels
+    pub fn as_word_tree(&self) -> &WordTree<'a, Box<str>, W> {
+        &self.tree
+    }
+    /// Get the case folding policy that maps the [`char`]s of strings before they are split
+    /// into grapheme clusters
+    pub fn case_folding(&self) -> CaseFolding {
+        self.case_folding
+    }
+    /// Get the Unicode normalization that is applied to strings before case folding
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+    /// Get the max depth of the tree
+    ///
+    /// Measured in number of grapheme cluster edges from the root node
+    /// to the deepest node in the tree.
+    ///
+    /// In a [fully well-formed](`Self::is_fully_well_formed`) grapheme tree, this depth
+    /// corresponds to the length in user-perceived characters of the longest word in the tree.
+    pub fn get_max_depth(&self) -> usize {
+        self.tree.get_max_depth()
+    }
+    /// The tree is *fully well-formed* as long as every leaf node corresponds to a word `W`,
+    /// same as for [`WordTree::is_fully_well_formed`].
+    pub fn is_fully_well_formed(&self) -> bool {
+        self.tree.is_fully_well_formed()
+    }
+    /// Returns an iterator over the words `W` of a grapheme tree
+    pub fn words(&self) -> WordTreeWords<'_, 'a, Box<str>, W> {
+        self.tree.words()
+    }
+    /// Get the number of words in the tree
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+    /// Returns `true` if the tree contains no words
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+    /// Get the number of nodes in the tree, including the root node
+    pub fn node_count(&self) -> usize {
+        self.tree.node_count()
+    }
+    /// Look up the word `W` for the given string
+    pub fn get(&self, word: &str) -> Option<&W> {
+        let s = self.edge_string(word);
+        self.tree
+            .find_edge_by(s.graphemes(true), grapheme_cmp)
+            .and_then(|edge| edge.child_node.word.as_ref())
+    }
+    /// Look up the index of the word for the given string
+    pub fn get_index(&self, word: &str) -> Option<usize> {
+        let s = self.edge_string(word);
+        self.tree
+            .find_edge_by(s.graphemes(true), grapheme_cmp)
+            .filter(|edge| edge.child_node.word.is_some())
+            .map(|edge| *edge.idx_range.start())
+    }
+    /// Look up the word `W` at the given index
+    pub fn get_by_index(&self, idx: usize) -> Option<&W> {
+        self.tree.get_by_index(idx)
+    }
+    /// Get the range of indices of the words that start with the given prefix
+    ///
+    /// The prefix must end at a grapheme cluster boundary of the words,
+    /// so that for example `e` is not a prefix of `é` written as `e` followed by U+0301.
+    /// The empty prefix matches every word in the tree.
+    pub fn prefix_range(&self, prefix: &str) -> Option<RangeInclusive<usize>> {
+        let s = self.edge_string(prefix);
+        self.tree
+            .find_prefix_edge_by(s.graphemes(true), grapheme_cmp)
+    }
+    /// Returns an iterator over the words `W` that start with the given prefix
+    pub fn words_with_prefix(&self, prefix: &str) -> WordTreeWords<'_, 'a, Box<str>, W> {
+        let s = self.edge_string(prefix);
+        self.tree
+            .words_with_prefix_by(s.graphemes(true), grapheme_cmp)
+    }
+    /// Map a string to the string of the edges along its path in the tree
+    fn edge_string(&self, s: &str) -> String {
+        edge_chars(s, self.case_folding, self.normalization).collect()
+    }
+}
+
+fn grapheme_cmp<L: AsRef<str>>(label: &L, grapheme: &&str) -> Ordering {
+    label.as_ref().cmp(*grapheme)
+}
+
+/// Builds a [`WordGraphemeTreeRootNode`] from words inserted in any order
+///
+/// The edges of every node are sorted by grapheme cluster, and the `idx_range`s of the edges
+/// are computed when the tree is built, same as for [`WordTreeBuilder`].
+pub struct WordGraphemeTreeBuilder<W> {
+    tree: WordTreeBuilder<Box<str>, W>,
+    case_folding: CaseFolding,
+    normalization: Normalization,
+}
+
+impl<W> Default for WordGraphemeTreeBuilder<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W> WordGraphemeTreeBuilder<W> {
+    /// Create a builder for an empty tree,
+    /// with the default [`CaseFolding`] policy and no [`Normalization`]
+    pub fn new() -> Self {
+        Self {
+            tree: WordTreeBuilder::new(),
+            case_folding: CaseFolding::default(),
+            normalization: Normalization::default(),
+        }
+    }
+    /// Set the [`CaseFolding`] policy of the tree
+    ///
+    /// Must be set before any words are inserted, so that all words are mapped the same way.
+    pub fn with_case_folding(mut self, case_folding: CaseFolding) -> Self {
+        assert!(self.is_empty(), "case folding set after inserting words");
+        self.case_folding = case_folding;
+        self
+    }
+    /// Set the [`Normalization`] of the tree
+    ///
+    /// Must be set before any words are inserted, so that all words are mapped the same way.
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        assert!(self.is_empty(), "normalization set after inserting words");
+        self.normalization = normalization;
+        self
+    }
+    /// Insert a word `W` under the given string
+    ///
+    /// Returns the word that was previously inserted under the same string, if any.
+    /// Empty strings cannot be represented in the tree and are ignored, in which case
+    /// the word is handed back.
+    pub fn insert(&mut self, s: &str, word: W) -> Option<W> {
+        let s = edge_chars(s, self.case_folding, self.normalization).collect::<String>();
+        self.tree.insert(s.graphemes(true).map(Box::from), word)
+    }
+    /// Get the number of words inserted so far
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+    /// Returns `true` if no words have been inserted yet
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+    /// Build the tree
+    pub fn build<'a>(self) -> WordGraphemeTreeRootNode<'a, W> {
+        WordGraphemeTreeRootNode {
+            tree: self.tree.build(),
+            case_folding: self.case_folding,
+            normalization: self.normalization,
+        }
+    }
+}
+
+impl<S: AsRef<str>, W> FromIterator<(S, W)> for WordGraphemeTreeRootNode<'_, W> {
+    fn from_iter<I: IntoIterator<Item = (S, W)>>(iter: I) -> Self {
+        let mut builder = WordGraphemeTreeBuilder::new();
+        for (s, word) in iter {
+            builder.insert(s.as_ref(), word);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::WordCharTreeRootNode;
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE_WORDS: [&str; 5] = ["cafe\u{301}", "cafe", "👍🏽", "👍🏽ok", "🇳🇴"];
+
+    fn example_tree() -> WordGraphemeTreeRootNode<'static, &'static str> {
+        EXAMPLE_WORDS.iter().map(|&w| (w, w)).collect()
+    }
+
+    #[test]
+    fn test_max_depth_counts_user_perceived_chars() {
+        let tree = example_tree();
+        assert_eq!(tree.get_max_depth(), 4);
+        let char_tree: WordCharTreeRootNode<&str> = EXAMPLE_WORDS.iter().map(|&w| (w, w)).collect();
+        assert_eq!(char_tree.get_max_depth(), 5);
+    }
+
+    #[test]
+    fn test_words_in_grapheme_order() {
+        let tree = example_tree();
+        assert_eq!(tree.len(), 5);
+        assert!(tree.is_fully_well_formed());
+        assert_eq!(
+            tree.words().copied().collect::<Vec<_>>(),
+            ["cafe", "cafe\u{301}", "🇳🇴", "👍🏽", "👍🏽ok"]
+        );
+        for (idx, word) in tree.words().enumerate() {
+            assert_eq!(tree.get_index(word), Some(idx));
+            assert_eq!(tree.get_by_index(idx), Some(word));
+        }
+    }
+
+    #[test_case("CAFE\u{301}", Some("cafe\u{301}") ; "combining mark")]
+    #[test_case("👍🏽OK", Some("👍🏽ok") ; "emoji modifier")]
+    #[test_case("👍", None ; "emoji without modifier")]
+    #[test_case("🇳", None ; "half of flag")]
+    fn test_get(s: &str, expected: Option<&str>) {
+        assert_eq!(example_tree().get(s).copied(), expected);
+    }
+
+    #[test_case("caf", Some(0..=1))]
+    #[test_case("cafe", Some(0..=0))]
+    #[test_case("cafe\u{301}", Some(1..=1))]
+    #[test_case("👍🏽", Some(3..=4) ; "emoji modifier")]
+    #[test_case("👍", None ; "emoji without modifier")]
+    #[test_case("", Some(0..=4) ; "empty")]
+    fn test_prefix_range(prefix: &str, expected: Option<RangeInclusive<usize>>) {
+        let tree = example_tree();
+        assert_eq!(tree.prefix_range(prefix), expected);
+        assert_eq!(
+            tree.words_with_prefix(prefix).count(),
+            expected.map_or(0, |range| range.count())
+        );
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn test_nfc_matches_precomposed() {
+        let mut builder = WordGraphemeTreeBuilder::new().with_normalization(Normalization::Nfc);
+        builder.insert("cafe\u{301}", ());
+        let tree = builder.build();
+        assert_eq!(tree.get_max_depth(), 4);
+        assert_eq!(tree.get_index("café"), Some(0));
+    }
+}
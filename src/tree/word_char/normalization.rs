@@ -0,0 +1,150 @@
+#[cfg(feature = "normalization")]
+use std::iter::{Filter, Map};
+
+#[cfg(feature = "normalization")]
+use unicode_normalization::{
+    char::is_combining_mark, Decompositions, Recompositions, UnicodeNormalization,
+};
+
+use core::str::Chars;
+
+use super::{CaseFolding, FoldedChars};
+
+/// Unicode normalization of strings before they are mapped to the [`char`] edges of a tree
+///
+/// Like the [`CaseFolding`] policy, the normalization is recorded on the tree when it is built,
+/// and the same normalization is used for every lookup in the tree. It is applied before
+/// case folding, so that chars which only become foldable once they are decomposed
+/// or stripped, such as the `E` of `È` for [`CaseFolding::Ascii`], are folded as well.
+///
+/// All forms other than [`Self::None`] require the `normalization` feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// Leave strings as they are
+    #[default]
+    None,
+    /// Normalization Form C (canonical composition),
+    /// so that the precomposed `é` and `e` followed by U+0301 match each other
+    #[cfg(feature = "normalization")]
+    Nfc,
+    /// Normalization Form KD (compatibility decomposition),
+    /// so that in addition compatibility forms such as `ﬁ` and `fi` match each other
+    #[cfg(feature = "normalization")]
+    Nfkd,
+    /// Normalization Form KD, followed by removal of all combining marks,
+    /// so that for example `creme` matches `crème`.
+    ///
+    /// Letters with a stroke, which have no decomposition, are also mapped to their base letter,
+    /// so that for example `soren` matches `søren` and `lodz` matches `łódź`.
+    #[cfg(feature = "normalization")]
+    StripAccents,
+}
+
+impl Normalization {
+    /// Returns an iterator over the chars, normalized by this form
+    pub fn normalize<I: Iterator<Item = char>>(self, chars: I) -> Normalized<I> {
+        Normalized(match self {
+            Self::None => NormalizedInner::None(chars),
+            #[cfg(feature = "normalization")]
+            Self::Nfc => NormalizedInner::Nfc(chars.nfc()),
+            #[cfg(feature = "normalization")]
+            Self::Nfkd => NormalizedInner::Nfkd(chars.nfkd()),
+            #[cfg(feature = "normalization")]
+            Self::StripAccents => {
+                let without_marks = chars
+                    .nfkd()
+                    .filter(is_not_combining_mark as fn(&char) -> bool);
+                NormalizedInner::StripAccents(without_marks.map(strip_stroke as fn(char) -> char))
+            }
+        })
+    }
+}
+
+/// An iterator over chars, normalized by a [`Normalization`] form
+pub struct Normalized<I: Iterator<Item = char>>(NormalizedInner<I>);
+
+enum NormalizedInner<I: Iterator<Item = char>> {
+    None(I),
+    #[cfg(feature = "normalization")]
+    Nfc(Recompositions<I>),
+    #[cfg(feature = "normalization")]
+    Nfkd(Decompositions<I>),
+    #[cfg(feature = "normalization")]
+    StripAccents(StripAccents<I>),
+}
+
+/// The NFKD decomposition of chars without combining marks and with strokes removed
+#[cfg(feature = "normalization")]
+type StripAccents<I> = Map<Filter<Decompositions<I>, fn(&char) -> bool>, fn(char) -> char>;
+
+#[cfg(feature = "normalization")]
+fn is_not_combining_mark(c: &char) -> bool {
+    !is_combining_mark(*c)
+}
+
+/// Map a letter with a stroke to its base letter
+#[cfg(feature = "normalization")]
+fn strip_stroke(c: char) -> char {
+    match c {
+        'ø' => 'o',
+        'Ø' => 'O',
+        'đ' => 'd',
+        'Đ' => 'D',
+        'ħ' => 'h',
+        'Ħ' => 'H',
+        'ł' => 'l',
+        'Ł' => 'L',
+        'ŧ' => 't',
+        'Ŧ' => 'T',
+        _ => c,
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Normalized<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        match &mut self.0 {
+            NormalizedInner::None(chars) => chars.next(),
+            #[cfg(feature = "normalization")]
+            NormalizedInner::Nfc(chars) => chars.next(),
+            #[cfg(feature = "normalization")]
+            NormalizedInner::Nfkd(chars) => chars.next(),
+            #[cfg(feature = "normalization")]
+            NormalizedInner::StripAccents(chars) => chars.next(),
+        }
+    }
+}
+
+/// Map the chars of a string to the chars of the edges along its path in a tree
+pub(super) fn edge_chars(
+    s: &str,
+    case_folding: CaseFolding,
+    normalization: Normalization,
+) -> FoldedChars<Normalized<Chars<'_>>> {
+    case_folding.fold_chars(normalization.normalize(s.chars()))
+}
+
+#[cfg(all(test, feature = "normalization"))]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Normalization::None, "cre\u{300}me", "cre\u{300}me")]
+    #[test_case(Normalization::Nfc, "Cre\u{300}me", "crème")]
+    #[test_case(Normalization::Nfkd, "Crème", "cre\u{300}me")]
+    #[test_case(Normalization::Nfkd, "ﬁle", "file")]
+    #[test_case(Normalization::StripAccents, "Crème Brûlée", "creme brulee")]
+    #[test_case(Normalization::StripAccents, "Año", "ano")]
+    fn test_edge_chars(normalization: Normalization, s: &str, expected: &str) {
+        let chars = edge_chars(s, CaseFolding::Lowercase, normalization);
+        assert_eq!(chars.collect::<String>(), expected);
+    }
+
+    #[test_case(CaseFolding::Lowercase, "Søren Łódź", "soren lodz")]
+    #[test_case(CaseFolding::Ascii, "ØRESUND", "oresund")]
+    fn test_strip_accents_letters_with_stroke(case_folding: CaseFolding, s: &str, expected: &str) {
+        let chars = edge_chars(s, case_folding, Normalization::StripAccents);
+        assert_eq!(chars.collect::<String>(), expected);
+    }
+}
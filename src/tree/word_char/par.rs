@@ -0,0 +1,52 @@
+use rayon::prelude::*;
+
+use super::WordCharTreeRootNode;
+use crate::ParWords;
+
+impl<'a, W: Sync> WordCharTreeRootNode<'a, W> {
+    /// Returns a parallel iterator over the words `W` of a word char tree
+    ///
+    /// See [`WordTree::par_words`](`crate::WordTree::par_words`) for how the work is split.
+    pub fn par_words(&self) -> ParWords<'_, 'a, char, W> {
+        self.tree.par_words()
+    }
+    /// Look up the words `W` for many strings in parallel
+    ///
+    /// Returns the result of [`Self::get`] for each of the strings, in the same order.
+    pub fn get_many<S: AsRef<str> + Sync>(&self, words: &[S]) -> Vec<Option<&W>> {
+        words
+            .par_iter()
+            .map(|word| self.get(word.as_ref()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::*;
+
+    #[test]
+    fn test_par_words_matches_words() {
+        let words = EXAMPLE_WORDLIST_7.par_words().collect::<Vec<_>>();
+        assert_eq!(words, EXAMPLE_WORDLIST_7.words().collect::<Vec<_>>());
+        assert_eq!(EXAMPLE_WORDLIST_EMPTY.par_words().count(), 0);
+    }
+
+    #[test]
+    fn test_par_words_large_tree() {
+        let tree = (0..10_000)
+            .map(|n| (format!("{n:x}"), n))
+            .collect::<WordCharTreeRootNode<u32>>();
+        let words = tree.par_words().copied().collect::<Vec<_>>();
+        assert_eq!(words, tree.words().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_get_many() {
+        assert_eq!(
+            EXAMPLE_WORDLIST_7.get_many(&["Zoo", "zo", "ant"]),
+            [Some(&ExampleWords7::Zoo), None, Some(&ExampleWords7::Ant)]
+        );
+    }
+}
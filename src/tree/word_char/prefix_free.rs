@@ -0,0 +1,152 @@
+use super::{DiffWord, WordCharTreeEdge, WordCharTreeRootNode};
+
+/// Which words [`WordCharTreeRootNode::make_prefix_free`] removes when one word
+/// is a prefix of another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixFreeStrategy {
+    /// Remove every word that is a prefix of another word, so that `arm` is removed
+    /// in favor of `army`
+    DropShorter,
+    /// Remove every word that has another word as a prefix, so that `army` is removed
+    /// in favor of `arm`
+    DropLonger,
+    /// Keep as many words as possible
+    ///
+    /// This is a maximum independent set of the words, where two words are in conflict
+    /// when one is a prefix of the other. Among the selections with the most words,
+    /// the shorter words are kept, so that `arm` is kept over `army`,
+    /// but `army` and `arms` are kept over `arm`.
+    KeepMost,
+}
+
+/// The words removed by [`WordCharTreeRootNode::make_prefix_free`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefixFreeReport {
+    /// The removed words, in order, with their index in the original tree
+    pub removed: Vec<DiffWord>,
+}
+
+impl<W: Clone> WordCharTreeRootNode<'_, W> {
+    /// Create a new tree where no word is a prefix of another word,
+    /// by removing words according to the given strategy
+    ///
+    /// The new tree is [suitable for iterative char search](`Self::is_suitable_for_iterative_char_search`)
+    /// and [fully well-formed](`Self::is_fully_well_formed`).
+    /// Words are identified by the string of the [`char`]s along their path,
+    /// same as in [`diff`](`crate::diff`).
+    pub fn make_prefix_free<'b>(
+        &self,
+        strategy: PrefixFreeStrategy,
+    ) -> (WordCharTreeRootNode<'b, W>, PrefixFreeReport) {
+        let mut removed = vec![];
+        select_edges(&self.tree.edges, strategy, &mut String::new(), &mut removed);
+        // The words are visited in the order of their paths, which is also the order
+        // of the path strings, so that the removed words can be searched for.
+        let tree = self.filter(|s, _| {
+            removed
+                .binary_search_by(|removed| removed.word.as_str().cmp(s))
+                .is_err()
+        });
+        (tree, PrefixFreeReport { removed })
+    }
+}
+
+/// Choose the words to remove under the given edges, and return the number of words kept
+fn select_edges<W>(
+    edges: &[WordCharTreeEdge<'_, W>],
+    strategy: PrefixFreeStrategy,
+    path: &mut String,
+    removed: &mut Vec<DiffWord>,
+) -> usize {
+    let mut kept = 0;
+    for edge in edges {
+        path.push(edge.label);
+        let child_edges = &edge.child_node.edges[..];
+        if edge.child_node.word.is_none() {
+            kept += select_edges(child_edges, strategy, path, removed);
+        } else if strategy == PrefixFreeStrategy::DropLonger {
+            remove_all(child_edges, path, removed);
+            kept += 1;
+        } else {
+            let this_word = DiffWord {
+                word: path.clone(),
+                index: *edge.idx_range.start(),
+            };
+            let mut removed_below = vec![];
+            let kept_below = select_edges(child_edges, strategy, path, &mut removed_below);
+            if kept_below == 0 {
+                kept += 1;
+            } else if strategy == PrefixFreeStrategy::KeepMost && kept_below == 1 {
+                remove_all(child_edges, path, removed);
+                kept += 1;
+            } else {
+                removed.push(this_word);
+                removed.append(&mut removed_below);
+                kept += kept_below;
+            }
+        }
+        path.pop();
+    }
+    kept
+}
+
+/// Remove every word under the given edges
+fn remove_all<W>(
+    edges: &[WordCharTreeEdge<'_, W>],
+    path: &mut String,
+    removed: &mut Vec<DiffWord>,
+) {
+    for edge in edges {
+        path.push(edge.label);
+        if edge.child_node.word.is_some() {
+            removed.push(DiffWord {
+                word: path.clone(),
+                index: *edge.idx_range.start(),
+            });
+        }
+        remove_all(&edge.child_node.edges, path, removed);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::*;
+    use test_case::test_case;
+
+    const WORDS: &[&str] = &["arm", "arms", "army", "man", "mango", "zoo"];
+
+    #[test_case(PrefixFreeStrategy::DropShorter, &["arms", "army", "mango", "zoo"], &[("arm", 0), ("man", 3)])]
+    #[test_case(PrefixFreeStrategy::DropLonger, &["arm", "man", "zoo"], &[("arms", 1), ("army", 2), ("mango", 4)])]
+    #[test_case(PrefixFreeStrategy::KeepMost, &["arms", "army", "man", "zoo"], &[("arm", 0), ("mango", 4)])]
+    fn test_make_prefix_free(
+        strategy: PrefixFreeStrategy,
+        expected: &[&str],
+        expected_removed: &[(&str, usize)],
+    ) {
+        let tree = example_tree(WORDS);
+        assert!(!tree.is_suitable_for_iterative_char_search());
+        let (prefix_free, report) = tree.make_prefix_free(strategy);
+        assert_eq!(prefix_free.words().collect::<Vec<_>>(), expected);
+        assert!(prefix_free.is_suitable_for_iterative_char_search());
+        assert!(prefix_free.is_fully_well_formed());
+        assert_eq!(
+            report
+                .removed
+                .iter()
+                .map(|removed| (removed.word.as_str(), removed.index))
+                .collect::<Vec<_>>(),
+            expected_removed
+        );
+    }
+
+    #[test_case(PrefixFreeStrategy::DropShorter)]
+    #[test_case(PrefixFreeStrategy::DropLonger)]
+    #[test_case(PrefixFreeStrategy::KeepMost)]
+    fn test_make_prefix_free_keeps_suitable_tree(strategy: PrefixFreeStrategy) {
+        let (prefix_free, report) = EXAMPLE_WORDLIST_1.make_prefix_free(strategy);
+        assert!(report.removed.is_empty());
+        assert_eq!(prefix_free.len(), EXAMPLE_WORDLIST_1.len());
+    }
+}
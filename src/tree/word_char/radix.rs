@@ -0,0 +1,338 @@
+use std::ops::RangeInclusive;
+use std::slice;
+
+use super::normalization::edge_chars;
+use super::{
+    CaseFolding, Edges, Normalization, WordCharTreeEdge, WordCharTreeNode, WordCharTreeRootNode,
+    WordTree, WordTreeEdge, WordTreeNode,
+};
+use crate::WordTreeWords;
+
+/// The root node of a radix tree, a path-compressed variant of the word char tree
+///
+/// Where a word char tree has one edge per [`char`], a radix tree merges each run of
+/// nodes that have no word and exactly one child edge into a single edge, whose label
+/// is the lowercase string of the chars along the run.
+/// The [`CaseFolding`] policy and the [`Normalization`] of the word char tree are kept
+/// for lookups in the radix tree.
+/// The tree itself is a [`WordTree`] with string labels. It always owns its edges,
+/// so it has no lifetime, and its words `W` cannot borrow either.
+///
+/// A radix tree can be converted to and from a [`WordCharTreeRootNode`] with [`From`].
+pub struct WordRadixTreeRootNode<W: 'static> {
+    tree: WordTree<'static, Box<str>, W>,
+    case_folding: CaseFolding,
+    normalization: Normalization,
+}
+
+type WordRadixTreeEdge<W> = WordTreeEdge<'static, Box<str>, W>;
+
+impl<W: 'static> WordRadixTreeRootNode<W> {
+    /// Get the underlying [`WordTree`] with string labels
+    pub fn as_word_tree(&self) -> &WordTree<'static, Box<str>, W> {
+        &self.tree
+    }
+    /// Get the case folding policy that maps the [`char`]s of strings to the edge labels
+    pub fn case_folding(&self) -> CaseFolding {
+        self.case_folding
+    }
+    /// Get the Unicode normalization that is applied to strings before case folding
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+    /// Get the max depth of the tree
+    ///
+    /// Measured in number of lowercase [`char`]s along the edge labels from the root node
+    /// to the deepest node in the tree, same as for [`WordCharTreeRootNode::get_max_depth`].
+    pub fn get_max_depth(&self) -> usize {
+        edges_max_depth(&self.tree.edges, 0)
+    }
+    /// The tree is *fully well-formed* as long as every leaf node corresponds to a word `W`,
+    /// same as for [`WordTree::is_fully_well_formed`].
+    pub fn is_fully_well_formed(&self) -> bool {
+        self.tree.is_fully_well_formed()
+    }
+    /// The tree is *suitable for iterative search* as long as every non-leaf node has
+    /// no word `W`, same as for [`WordTree::is_suitable_for_iterative_search`].
+    pub fn is_suitable_for_iterative_search(&self) -> bool {
+        self.tree.is_suitable_for_iterative_search()
+    }
+    /// Returns an iterator over the words `W` of a radix tree
+    pub fn words(&self) -> WordTreeWords<'_, 'static, Box<str>, W> {
+        self.tree.words()
+    }
+    /// Get the number of words in the tree
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+    /// Returns `true` if the tree contains no words
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+    /// Get the number of nodes in the tree, including the root node
+    pub fn node_count(&self) -> usize {
+        self.tree.node_count()
+    }
+    /// Look up the word `W` for the given string
+    ///
+    /// The string is lowercased before matching it against the edge labels.
+    pub fn get(&self, word: &str) -> Option<&W> {
+        match self.find_edge(word)? {
+            (edge, true) => edge.child_node.word.as_ref(),
+            (_, false) => None,
+        }
+    }
+    /// Look up the index of the word for the given string
+    pub fn get_index(&self, word: &str) -> Option<usize> {
+        match self.find_edge(word)? {
+            (edge, true) if edge.child_node.word.is_some() => Some(*edge.idx_range.start()),
+            _ => None,
+        }
+    }
+    /// Look up the word `W` at the given index
+    pub fn get_by_index(&self, idx: usize) -> Option<&W> {
+        self.tree.get_by_index(idx)
+    }
+    /// Get the range of indices of the words that start with the given prefix
+    ///
+    /// The prefix may end partway through an edge label.
+    /// The empty prefix matches every word in the tree.
+    pub fn prefix_range(&self, prefix: &str) -> Option<RangeInclusive<usize>> {
+        if prefix.is_empty() {
+            return (!self.is_empty()).then(|| 0..=self.len() - 1);
+        }
+        self.find_edge(prefix)
+            .map(|(edge, _)| edge.idx_range.clone())
+    }
+    /// Returns an iterator over the words `W` that start with the given prefix
+    ///
+    /// The prefix may end partway through an edge label.
+    pub fn words_with_prefix(&self, prefix: &str) -> WordTreeWords<'_, 'static, Box<str>, W> {
+        if prefix.is_empty() {
+            return self.words();
+        }
+        match self.find_edge(prefix) {
+            Some((edge, _)) => WordTreeWords::new(slice::from_ref(edge)),
+            None => WordTreeWords::new(&[]),
+        }
+    }
+    /// Find the edge along which the given string ends
+    ///
+    /// The returned flag is `true` if the string ends exactly at the child node of the edge,
+    /// and `false` if it ends partway through the edge label.
+    fn find_edge(&self, s: &str) -> Option<(&WordRadixTreeEdge<W>, bool)> {
+        let s = edge_chars(s, self.case_folding, self.normalization).collect::<String>();
+        let mut rest = s.as_str();
+        let mut edges: &[WordRadixTreeEdge<W>] = &self.tree.edges;
+        loop {
+            let first = rest.chars().next()?;
+            // Labels of sibling edges start with distinct chars, in sorted order.
+            let edge = edges
+                .binary_search_by(|edge| edge.label.chars().next().cmp(&Some(first)))
+                .ok()
+                .map(|i| &edges[i])?;
+            if let Some(remaining) = rest.strip_prefix(&*edge.label) {
+                if remaining.is_empty() {
+                    return Some((edge, true));
+                }
+                rest = remaining;
+                edges = &edge.child_node.edges;
+            } else if edge.label.starts_with(rest) {
+                return Some((edge, false));
+            } else {
+                return None;
+            }
+        }
+    }
+}
+
+/// Get the max depth in [`char`]s below the given edges,
+/// where `depth` is the depth of the node that the edges start from
+fn edges_max_depth<W: 'static>(edges: &[WordRadixTreeEdge<W>], depth: usize) -> usize {
+    edges
+        .iter()
+        .map(|edge| edges_max_depth(&edge.child_node.edges, depth + edge.label.chars().count()))
+        .max()
+        .unwrap_or(depth)
+}
+
+impl<W: Clone + 'static> From<&WordCharTreeRootNode<'_, W>> for WordRadixTreeRootNode<W> {
+    fn from(root: &WordCharTreeRootNode<'_, W>) -> Self {
+        Self {
+            tree: WordTree {
+                edges: compress_edges(&root.tree.edges),
+            },
+            case_folding: root.case_folding,
+            normalization: root.normalization,
+        }
+    }
+}
+
+impl<'a, W: Clone + 'static> From<&WordRadixTreeRootNode<W>> for WordCharTreeRootNode<'a, W> {
+    fn from(root: &WordRadixTreeRootNode<W>) -> Self {
+        Self {
+            tree: WordTree {
+                edges: expand_edges(&root.tree.edges),
+            },
+            case_folding: root.case_folding,
+            normalization: root.normalization,
+        }
+    }
+}
+
+fn compress_edges<W: Clone + 'static>(
+    edges: &[WordCharTreeEdge<'_, W>],
+) -> Edges<'static, WordRadixTreeEdge<W>> {
+    let edges = edges
+        .iter()
+        .map(|edge| {
+            let mut label = String::from(edge.label);
+            let mut node = &edge.child_node;
+            while node.word.is_none() && node.edges.len() == 1 {
+                label.push(node.edges[0].label);
+                node = &node.edges[0].child_node;
+            }
+            WordTreeEdge {
+                label: label.into_boxed_str(),
+                idx_range: edge.idx_range.clone(),
+                child_node: WordTreeNode {
+                    word: node.word.clone(),
+                    edges: compress_edges(&node.edges),
+                },
+            }
+        })
+        .collect();
+    Edges::Owned(edges)
+}
+
+fn expand_edges<'a, W: Clone + 'static>(
+    edges: &[WordRadixTreeEdge<W>],
+) -> Edges<'a, WordCharTreeEdge<'a, W>> {
+    let edges = edges
+        .iter()
+        .map(|edge| {
+            let mut chars = edge.label.chars().rev();
+            let last = chars.next().expect("radix tree edge labels are non-empty");
+            let mut char_edge = WordCharTreeEdge {
+                label: last,
+                idx_range: edge.idx_range.clone(),
+                child_node: WordCharTreeNode {
+                    word: edge.child_node.word.clone(),
+                    edges: expand_edges(&edge.child_node.edges),
+                },
+            };
+            for label in chars {
+                char_edge = WordCharTreeEdge {
+                    label,
+                    idx_range: edge.idx_range.clone(),
+                    child_node: WordCharTreeNode {
+                        word: None,
+                        edges: Edges::Owned(vec![char_edge]),
+                    },
+                };
+            }
+            char_edge
+        })
+        .collect();
+    Edges::Owned(edges)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(EXAMPLE_WORDLIST_EMPTY)]
+    #[test_case(EXAMPLE_WORDLIST_1)]
+    #[test_case(EXAMPLE_WORDLIST_2)]
+    #[test_case(EXAMPLE_WORDLIST_3)]
+    #[test_case(EXAMPLE_WORDLIST_4)]
+    #[test_case(EXAMPLE_WORDLIST_5)]
+    #[test_case(EXAMPLE_WORDLIST_6)]
+    #[test_case(EXAMPLE_WORDLIST_7)]
+    fn test_positive_radix_round_trip<W>(root: WordCharTreeRootNode<W>)
+    where
+        W: Clone + std::fmt::Debug + PartialEq + 'static,
+    {
+        let radix = WordRadixTreeRootNode::from(&root);
+        assert_eq!(radix.len(), root.len());
+        assert_eq!(radix.get_max_depth(), root.get_max_depth());
+        assert_eq!(radix.is_fully_well_formed(), root.is_fully_well_formed());
+        assert_eq!(
+            radix.is_suitable_for_iterative_search(),
+            root.is_suitable_for_iterative_char_search()
+        );
+        assert_eq!(
+            radix.words().collect::<Vec<_>>(),
+            root.words().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            radix.words().rev().collect::<Vec<_>>(),
+            root.words().rev().collect::<Vec<_>>()
+        );
+        let round_trip = WordCharTreeRootNode::from(&radix);
+        assert_eq!(round_trip.node_count(), root.node_count());
+        assert_eq!(
+            round_trip.words().collect::<Vec<_>>(),
+            root.words().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_radix_node_count() {
+        let radix = WordRadixTreeRootNode::from(&EXAMPLE_WORDLIST_7);
+        assert_eq!(EXAMPLE_WORDLIST_7.node_count(), 41);
+        assert_eq!(radix.node_count(), 24);
+    }
+
+    #[test_case("zombie", Some(14))]
+    #[test_case("ZOMBIE", Some(14); "uppercase zombie")]
+    #[test_case("zomb", None)]
+    #[test_case("man", Some(4))]
+    #[test_case("mango", Some(6))]
+    #[test_case("mangos", None)]
+    #[test_case("i", Some(2))]
+    #[test_case("", None)]
+    fn test_radix_get_index(word: &str, expected_idx: Option<usize>) {
+        let radix = WordRadixTreeRootNode::from(&EXAMPLE_WORDLIST_7);
+        assert_eq!(radix.get_index(word), expected_idx);
+        assert_eq!(radix.get(word), EXAMPLE_WORDLIST_7.get(word));
+        if let Some(idx) = expected_idx {
+            assert_eq!(radix.get_by_index(idx), EXAMPLE_WORDLIST_7.get(word));
+        }
+    }
+
+    #[test_case("", Some(0..=15))]
+    #[test_case("z", Some(11..=15))]
+    #[test_case("zo", Some(14..=15))]
+    #[test_case("zom", Some(14..=14))]
+    #[test_case("ma", Some(3..=7))]
+    #[test_case("mang", Some(6..=6))]
+    #[test_case("q", None)]
+    #[test_case("zombies", None)]
+    fn test_radix_prefix_range(prefix: &str, expected_range: Option<RangeInclusive<usize>>) {
+        let radix = WordRadixTreeRootNode::from(&EXAMPLE_WORDLIST_7);
+        assert_eq!(radix.prefix_range(prefix), expected_range);
+        assert_eq!(EXAMPLE_WORDLIST_7.prefix_range(prefix), expected_range);
+        assert_eq!(
+            radix.words_with_prefix(prefix).collect::<Vec<_>>(),
+            EXAMPLE_WORDLIST_7
+                .words_with_prefix(prefix)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_radix_words_from_both_ends() {
+        let radix = WordRadixTreeRootNode::from(&EXAMPLE_WORDLIST_7);
+        let mut words = radix.words_with_prefix("man");
+        assert_eq!(words.len(), 3);
+        assert_eq!(words.next(), Some(&ExampleWords7::Man));
+        assert_eq!(words.next_back(), Some(&ExampleWords7::Mango));
+        assert_eq!(words.len(), 1);
+        assert_eq!(words.next_back(), Some(&ExampleWords7::Mane));
+        assert_eq!(words.next(), None);
+        assert_eq!(words.next_back(), None);
+    }
+}
@@ -0,0 +1,99 @@
+use super::{WordCharTreeEdge, WordCharTreeNode, WordCharTreeRootNode};
+
+impl<W> WordCharTreeRootNode<'_, W> {
+    /// Render the tree as text, drawn with box-drawing characters
+    ///
+    /// Nodes with a word are drawn as `●` and nodes without a word as `○`.
+    /// The first child edge of a node without a word is drawn on the same line as the node,
+    /// so that in a [fully well-formed](`Self::is_fully_well_formed`) tree, each line
+    /// after the root node ends with exactly one word, in the order of [`Self::words`].
+    ///
+    /// ```text
+    /// ○
+    /// ┣╸a╺○╸r╺○╸m╺●
+    /// ┃           ┗╸y╺●
+    /// ┗╸m╺○╸a╺○╸n╺●
+    /// ```
+    pub fn render(&self) -> String {
+        let mut out = String::from("○\n");
+        render_child_edges(&self.tree.edges, "", &mut out);
+        out
+    }
+}
+
+/// Render each edge on a new line, with the branch drawn after `prefix`
+fn render_child_edges<W>(edges: &[WordCharTreeEdge<'_, W>], prefix: &str, out: &mut String) {
+    for (i, edge) in edges.iter().enumerate() {
+        let is_last = i == edges.len() - 1;
+        out.push_str(prefix);
+        out.push(if is_last { '┗' } else { '┣' });
+        let child_prefix = format!("{prefix}{}   ", if is_last { ' ' } else { '┃' });
+        render_edge(edge, &child_prefix, out);
+    }
+}
+
+/// Render an edge and its child node, continuing the current line
+fn render_edge<W>(edge: &WordCharTreeEdge<'_, W>, prefix: &str, out: &mut String) {
+    out.push('╸');
+    out.push(edge.label);
+    out.push('╺');
+    render_node(&edge.child_node, prefix, out);
+}
+
+fn render_node<W>(node: &WordCharTreeNode<'_, W>, prefix: &str, out: &mut String) {
+    match (&node.word, node.edges.split_first()) {
+        (None, Some((first, rest))) => {
+            out.push('○');
+            let first_prefix = format!("{prefix}{}   ", if rest.is_empty() { ' ' } else { '┃' });
+            render_edge(first, &first_prefix, out);
+            render_child_edges(rest, prefix, out);
+        }
+        (word, _) => {
+            out.push(if word.is_some() { '●' } else { '○' });
+            out.push('\n');
+            render_child_edges(&node.edges, prefix, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use test_case::test_case;
+
+    #[test_case(EXAMPLE_WORDLIST_EMPTY, "○\n")]
+    #[test_case(
+        EXAMPLE_WORDLIST_2,
+        "○\n┣╸a╺○╸r╺○╸m╺●\n┃           ┗╸y╺●\n┗╸m╺○╸a╺○╸n╺●\n"
+    )]
+    #[test_case(EXAMPLE_WORDLIST_6, "○\n┗╸a╺●\n    ┗╸n╺●\n        ┗╸t╺●\n")]
+    fn test_render<W>(root: super::WordCharTreeRootNode<W>, expected: &str) {
+        assert_eq!(root.render(), expected);
+    }
+
+    #[test]
+    fn test_render_one_word_per_line() {
+        let expected = [
+            "○",
+            "┣╸a╺○╸n╺○╸t╺●",
+            "┃   ┗╸r╺○╸t╺●",
+            "┣╸i╺●",
+            "┣╸m╺○╸a╺○╸i╺○╸n╺●",
+            "┃   ┃   ┣╸n╺●",
+            "┃   ┃   ┃   ┣╸e╺●",
+            "┃   ┃   ┃   ┗╸g╺○╸o╺●",
+            "┃   ┃   ┗╸r╺○╸e╺●",
+            "┃   ┗╸o╺○╸r╺○╸e╺●",
+            "┣╸x╺○╸r╺○╸a╺○╸m╺●",
+            "┃           ┗╸y╺●",
+            "┗╸z╺○╸e╺○╸b╺○╸r╺○╸a╺●",
+            "    ┃   ┗╸r╺○╸o╺●",
+            "    ┣╸i╺○╸n╺○╸c╺●",
+            "    ┗╸o╺○╸m╺○╸b╺○╸i╺○╸e╺●",
+            "        ┗╸o╺●",
+        ];
+        let rendered = EXAMPLE_WORDLIST_7.render();
+        assert_eq!(rendered.lines().collect::<Vec<_>>(), expected);
+        assert_eq!(rendered.lines().count() - 1, EXAMPLE_WORDLIST_7.len());
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+use super::{
+    CaseFolding, Edges, Normalization, WordCharTreeEdge, WordCharTreeNode, WordCharTreeRootNode,
+    WordTree,
+};
+
+/// The key of the [`CaseFolding`] policy in the map of the root node
+const CASE_FOLDING_KEY: &str = "case_folding";
+
+fn case_folding_name(case_folding: CaseFolding) -> &'static str {
+    match case_folding {
+        CaseFolding::Lowercase => "lowercase",
+        CaseFolding::Full => "full",
+        CaseFolding::Ascii => "ascii",
+    }
+}
+
+fn case_folding_from_name(name: &str) -> Option<CaseFolding> {
+    [
+        CaseFolding::Lowercase,
+        CaseFolding::Full,
+        CaseFolding::Ascii,
+    ]
+    .into_iter()
+    .find(|&case_folding| case_folding_name(case_folding) == name)
+}
+
+/// The key of the [`Normalization`] in the map of the root node
+const NORMALIZATION_KEY: &str = "normalization";
+
+fn normalization_name(normalization: Normalization) -> &'static str {
+    match normalization {
+        Normalization::None => "none",
+        #[cfg(feature = "normalization")]
+        Normalization::Nfc => "nfc",
+        #[cfg(feature = "normalization")]
+        Normalization::Nfkd => "nfkd",
+        #[cfg(feature = "normalization")]
+        Normalization::StripAccents => "strip_accents",
+    }
+}
+
+fn normalization_from_name(name: &str) -> Option<Normalization> {
+    [
+        Normalization::None,
+        #[cfg(feature = "normalization")]
+        Normalization::Nfc,
+        #[cfg(feature = "normalization")]
+        Normalization::Nfkd,
+        #[cfg(feature = "normalization")]
+        Normalization::StripAccents,
+    ]
+    .into_iter()
+    .find(|&normalization| normalization_name(normalization) == name)
+}
+
+/// The tree is serialized as a nested map from each [`char`] to the edge for that char.
+///
+/// Unless the tree has the default [`CaseFolding`] policy, the map of the root node also holds
+/// the policy under the key `case_folding`, as one of `"lowercase"`, `"full"` or `"ascii"`.
+/// Likewise, unless the tree has no [`Normalization`], the map of the root node holds it under
+/// the key `normalization`, as one of `"nfc"`, `"nfkd"` or `"strip_accents"`.
+///
+/// Each edge has the following fields:
+/// - `idx_range`: The start and end of the `idx_range` of the edge.
+/// - `word`: The word `W` of the child node of the edge. Omitted if the child node has no word.
+/// - `edges`: The map of the child edges of the child node. Omitted if there are none.
+impl<W: Serialize> Serialize for WordCharTreeRootNode<'_, W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let has_case_folding = self.case_folding != CaseFolding::default();
+        let has_normalization = self.normalization != Normalization::default();
+        let len = self.tree.edges.len() + has_case_folding as usize + has_normalization as usize;
+        let mut map = serializer.serialize_map(Some(len))?;
+        if has_case_folding {
+            map.serialize_entry(CASE_FOLDING_KEY, case_folding_name(self.case_folding))?;
+        }
+        if has_normalization {
+            map.serialize_entry(NORMALIZATION_KEY, normalization_name(self.normalization))?;
+        }
+        for edge in self.tree.edges.iter() {
+            map.serialize_entry(&edge.label, edge)?;
+        }
+        map.end()
+    }
+}
+
+struct EdgeMap<'t, 'a, W>(&'t [WordCharTreeEdge<'a, W>]);
+
+impl<W: Serialize> Serialize for EdgeMap<'_, '_, W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|edge| (edge.label, edge)))
+    }
+}
+
+impl<W: Serialize> Serialize for WordCharTreeEdge<'_, W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("WordCharTreeEdge", 3)?;
+        state.serialize_field("idx_range", &(self.idx_range.start(), self.idx_range.end()))?;
+        match &self.child_node.word {
+            Some(word) => state.serialize_field("word", word)?,
+            None => state.skip_field("word")?,
+        }
+        if self.child_node.edges.is_empty() {
+            state.skip_field("edges")?;
+        } else {
+            state.serialize_field("edges", &EdgeMap(&self.child_node.edges))?;
+        }
+        state.end()
+    }
+}
+
+/// An edge as it is read, before its `idx_range` has been checked against its subtree
+struct RawEdge<W> {
+    idx_range: (usize, usize),
+    word: Option<W>,
+    edges: Vec<(char, RawEdge<W>)>,
+}
+
+struct RawEdgeMap<W>(Vec<(char, RawEdge<W>)>);
+
+impl<'de, W: Deserialize<'de>> Deserialize<'de> for RawEdgeMap<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EdgeMapVisitor<W>(PhantomData<W>);
+
+        impl<'de, W: Deserialize<'de>> Visitor<'de> for EdgeMapVisitor<W> {
+            type Value = RawEdgeMap<W>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map from chars to edges")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut edges: Vec<(char, RawEdge<W>)> = vec![];
+                while let Some((char_lowercase, edge)) = map.next_entry()? {
+                    edges.push((char_lowercase, edge));
+                }
+                sort_edges(&mut edges)?;
+                Ok(RawEdgeMap(edges))
+            }
+        }
+
+        deserializer.deserialize_map(EdgeMapVisitor(PhantomData))
+    }
+}
+
+impl<'de, W: Deserialize<'de>> Deserialize<'de> for RawEdge<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EdgeVisitor<W>(PhantomData<W>);
+
+        impl<'de, W: Deserialize<'de>> Visitor<'de> for EdgeVisitor<W> {
+            type Value = RawEdge<W>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a word char tree edge")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut idx_range = None;
+                let mut word = None;
+                let mut edges = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "idx_range" if idx_range.is_none() => idx_range = Some(map.next_value()?),
+                        "word" if word.is_none() => word = Some(map.next_value()?),
+                        "edges" if edges.is_none() => {
+                            edges = Some(map.next_value::<RawEdgeMap<W>>()?.0)
+                        }
+                        "idx_range" | "word" | "edges" => {
+                            return Err(de::Error::custom(format_args!("duplicate field `{key}`")))
+                        }
+                        _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+                    }
+                }
+                Ok(RawEdge {
+                    idx_range: idx_range.ok_or_else(|| de::Error::missing_field("idx_range"))?,
+                    word,
+                    edges: edges.unwrap_or_default(),
+                })
+            }
+        }
+
+        const FIELDS: &[&str] = &["idx_range", "word", "edges"];
+        deserializer.deserialize_struct("WordCharTreeEdge", FIELDS, EdgeVisitor(PhantomData))
+    }
+}
+
+/// Sort edges by char, and check that no char has more than one edge
+fn sort_edges<W, E: de::Error>(edges: &mut [(char, RawEdge<W>)]) -> Result<(), E> {
+    edges.sort_by_key(|(char_lowercase, _)| *char_lowercase);
+    match edges.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        Some(pair) => Err(E::custom(format_args!(
+            "duplicate edge for char {:?}",
+            pair[0].0
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The tree is deserialized from the representation described for [`Serialize`].
+///
+/// The `idx_range` of every edge is checked against the words in the subtree of the edge.
+impl<'de, W: Deserialize<'de>> Deserialize<'de> for WordCharTreeRootNode<'_, W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RootVisitor<W>(PhantomData<W>);
+
+        impl<'de, W: Deserialize<'de>> Visitor<'de> for RootVisitor<W> {
+            type Value = (CaseFolding, Normalization, Vec<(char, RawEdge<W>)>);
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map from chars to edges")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut case_folding = None;
+                let mut normalization = None;
+                let mut edges: Vec<(char, RawEdge<W>)> = vec![];
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == CASE_FOLDING_KEY {
+                        if case_folding.is_some() {
+                            return Err(de::Error::custom("duplicate field `case_folding`"));
+                        }
+                        let name = map.next_value::<String>()?;
+                        case_folding = Some(case_folding_from_name(&name).ok_or_else(|| {
+                            de::Error::custom(format_args!("unknown case folding {name:?}"))
+                        })?);
+                        continue;
+                    }
+                    if key == NORMALIZATION_KEY {
+                        if normalization.is_some() {
+                            return Err(de::Error::custom("duplicate field `normalization`"));
+                        }
+                        let name = map.next_value::<String>()?;
+                        normalization = Some(normalization_from_name(&name).ok_or_else(|| {
+                            de::Error::custom(format_args!("unknown normalization {name:?}"))
+                        })?);
+                        continue;
+                    }
+                    let mut chars = key.chars();
+                    let (Some(char_lowercase), None) = (chars.next(), chars.next()) else {
+                        return Err(de::Error::invalid_value(
+                            de::Unexpected::Str(&key),
+                            &"a char",
+                        ));
+                    };
+                    edges.push((char_lowercase, map.next_value()?));
+                }
+                sort_edges(&mut edges)?;
+                Ok((
+                    case_folding.unwrap_or_default(),
+                    normalization.unwrap_or_default(),
+                    edges,
+                ))
+            }
+        }
+
+        let (case_folding, normalization, edges) =
+            deserializer.deserialize_map(RootVisitor(PhantomData))?;
+        let mut next_idx = 0;
+        Ok(Self {
+            tree: WordTree {
+                edges: into_edges(edges, &mut next_idx)?,
+            },
+            case_folding,
+            normalization,
+        })
+    }
+}
+
+fn into_edges<'a, W, E: de::Error>(
+    raw_edges: Vec<(char, RawEdge<W>)>,
+    next_idx: &mut usize,
+) -> Result<Edges<'a, WordCharTreeEdge<'a, W>>, E> {
+    if raw_edges.is_empty() {
+        return Ok(Edges::default());
+    }
+    let mut edges = Vec::with_capacity(raw_edges.len());
+    for (char_lowercase, raw_edge) in raw_edges {
+        let start = *next_idx;
+        if raw_edge.word.is_some() {
+            *next_idx += 1;
+        }
+        let child_edges = into_edges(raw_edge.edges, next_idx)?;
+        if *next_idx == start || raw_edge.idx_range != (start, *next_idx - 1) {
+            return Err(E::custom(format_args!(
+                "inconsistent idx_range for edge {char_lowercase:?}"
+            )));
+        }
+        edges.push(WordCharTreeEdge {
+            label: char_lowercase,
+            idx_range: start..=*next_idx - 1,
+            child_node: WordCharTreeNode {
+                word: raw_edge.word,
+                edges: child_edges,
+            },
+        });
+    }
+    Ok(Edges::Owned(edges))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::WordCharTreeBuilder;
+    use super::*;
+
+    fn example_tree() -> WordCharTreeRootNode<'static, String> {
+        ["arm", "army", "man"]
+            .into_iter()
+            .map(|s| (s, s.to_uppercase()))
+            .collect()
+    }
+
+    #[test]
+    fn test_serialize_nested_representation() {
+        let json = serde_json::to_value(example_tree()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "a": {"idx_range": [0, 1], "edges": {
+                    "r": {"idx_range": [0, 1], "edges": {
+                        "m": {"idx_range": [0, 1], "word": "ARM", "edges": {
+                            "y": {"idx_range": [1, 1], "word": "ARMY"}
+                        }}
+                    }}
+                }},
+                "m": {"idx_range": [2, 2], "edges": {
+                    "a": {"idx_range": [2, 2], "edges": {
+                        "n": {"idx_range": [2, 2], "word": "MAN"}
+                    }}
+                }}
+            })
+        );
+    }
+
+    #[test]
+    fn test_positive_serde_round_trip() {
+        let root = example_tree();
+        let json = serde_json::to_string(&root).unwrap();
+        let read: WordCharTreeRootNode<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            read.words().collect::<Vec<_>>(),
+            root.words().collect::<Vec<_>>()
+        );
+        assert_eq!(read.get_index("army"), Some(1));
+        assert_eq!(read.node_count(), root.node_count());
+    }
+
+    #[test]
+    fn test_positive_serde_round_trip_case_folding() {
+        let mut builder = WordCharTreeBuilder::new().with_case_folding(CaseFolding::Full);
+        builder.insert("Straße", "STRASSE".to_string());
+        let root = builder.build();
+        let json = serde_json::to_value(&root).unwrap();
+        assert_eq!(json["case_folding"], "full");
+        let read: WordCharTreeRootNode<String> = serde_json::from_value(json).unwrap();
+        assert_eq!(read.case_folding(), CaseFolding::Full);
+        assert_eq!(read.get("STRASSE"), Some(&"STRASSE".to_string()));
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn test_positive_serde_round_trip_normalization() {
+        let mut builder =
+            WordCharTreeBuilder::new().with_normalization(Normalization::StripAccents);
+        builder.insert("crème", "CRÈME".to_string());
+        let json = serde_json::to_value(builder.build()).unwrap();
+        assert_eq!(json["normalization"], "strip_accents");
+        let read: WordCharTreeRootNode<String> = serde_json::from_value(json).unwrap();
+        assert_eq!(read.normalization(), Normalization::StripAccents);
+        assert_eq!(read.get("creme"), Some(&"CRÈME".to_string()));
+    }
+
+    #[test]
+    fn test_negative_deserialize_unknown_normalization() {
+        let json = r#"{"normalization": "nfd", "a": {"idx_range": [0, 0], "word": "A"}}"#;
+        let Err(err) = serde_json::from_str::<WordCharTreeRootNode<String>>(json) else {
+            panic!("unknown normalization was accepted");
+        };
+        assert!(err.to_string().contains("unknown normalization"));
+    }
+
+    #[test]
+    fn test_negative_deserialize_inconsistent_idx_range() {
+        let json = r#"{"a": {"idx_range": [0, 1], "word": "A"}}"#;
+        let Err(err) = serde_json::from_str::<WordCharTreeRootNode<String>>(json) else {
+            panic!("inconsistent idx_range was accepted");
+        };
+        assert!(err.to_string().contains("inconsistent idx_range"));
+    }
+
+    #[test]
+    fn test_negative_deserialize_unknown_field() {
+        let json = r#"{"a": {"idx_range": [0, 0], "word": "A", "wrod": "B"}}"#;
+        assert!(serde_json::from_str::<WordCharTreeRootNode<String>>(json).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use super::WordCharTreeRootNode;
+
+impl<W: Clone> WordCharTreeRootNode<'_, W> {
+    /// Create a new tree with the words of both trees,
+    /// see [`WordTree::union`](`crate::WordTree::union`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trees map strings to chars differently,
+    /// that is, if they have different case folding policies or normalizations.
+    pub fn union<'b>(
+        &self,
+        other: &WordCharTreeRootNode<'_, W>,
+        resolve: impl Fn(&W, &W) -> W,
+    ) -> WordCharTreeRootNode<'b, W> {
+        self.assert_same_mapping(other);
+        WordCharTreeRootNode {
+            tree: self.tree.union(&other.tree, resolve),
+            case_folding: self.case_folding,
+            normalization: self.normalization,
+        }
+    }
+    /// Create a new tree with the words that are in both trees,
+    /// see [`WordTree::intersection`](`crate::WordTree::intersection`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trees map strings to chars differently, same as [`Self::union`].
+    pub fn intersection<'b>(
+        &self,
+        other: &WordCharTreeRootNode<'_, W>,
+        resolve: impl Fn(&W, &W) -> W,
+    ) -> WordCharTreeRootNode<'b, W> {
+        self.assert_same_mapping(other);
+        WordCharTreeRootNode {
+            tree: self.tree.intersection(&other.tree, resolve),
+            case_folding: self.case_folding,
+            normalization: self.normalization,
+        }
+    }
+    /// Create a new tree with the words of this tree that are not in the other tree,
+    /// see [`WordTree::difference`](`crate::WordTree::difference`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trees map strings to chars differently, same as [`Self::union`].
+    pub fn difference<'b>(
+        &self,
+        other: &WordCharTreeRootNode<'_, W>,
+    ) -> WordCharTreeRootNode<'b, W> {
+        self.assert_same_mapping(other);
+        WordCharTreeRootNode {
+            tree: self.tree.difference(&other.tree),
+            case_folding: self.case_folding,
+            normalization: self.normalization,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::super::{CaseFolding, WordCharTreeBuilder};
+    use super::*;
+    use test_case::test_case;
+
+    fn words_of<'t>(tree: &'t WordCharTreeRootNode<String>) -> Vec<&'t str> {
+        tree.words().map(String::as_str).collect()
+    }
+
+    #[test_case(&["arm", "man"], &["army", "arm"], &["arm", "army", "man"] ; "shared prefix")]
+    #[test_case(&[], &["zoo"], &["zoo"] ; "empty")]
+    fn test_union(a: &[&str], b: &[&str], expected: &[&str]) {
+        let union = example_tree(a).union(&example_tree(b), |a, _| a.clone());
+        assert_eq!(words_of(&union), expected);
+        assert!(union.is_fully_well_formed());
+        for (idx, word) in expected.iter().enumerate() {
+            assert_eq!(union.get_index(word), Some(idx));
+        }
+    }
+
+    #[test]
+    fn test_union_resolves_words_in_both() {
+        let base = example_tree(&["arm", "man"]);
+        let additions: WordCharTreeRootNode<String> =
+            [("Arm", "ARM".to_string())].into_iter().collect();
+        let union = base.union(&additions, |a, b| format!("{a}/{b}"));
+        assert_eq!(words_of(&union), ["arm/ARM", "man"]);
+    }
+
+    #[test_case(&["arm", "army", "man"], &["army", "zoo"], &["army"] ; "deep word")]
+    #[test_case(&["arm", "man"], &["army", "mango"], &[] ; "prefixes only")]
+    fn test_intersection(a: &[&str], b: &[&str], expected: &[&str]) {
+        let intersection = example_tree(a).intersection(&example_tree(b), |a, _| a.clone());
+        assert_eq!(words_of(&intersection), expected);
+        assert!(intersection.is_fully_well_formed());
+        assert_eq!(intersection.len(), expected.len());
+    }
+
+    #[test_case(&["arm", "army", "man"], &["arm"], &["army", "man"] ; "keep longer word")]
+    #[test_case(&["arm", "army", "man"], &["army", "man"], &["arm"] ; "prune subtrees")]
+    fn test_difference(a: &[&str], b: &[&str], expected: &[&str]) {
+        let difference = example_tree(a).difference(&example_tree(b));
+        assert_eq!(words_of(&difference), expected);
+        assert!(difference.is_fully_well_formed());
+        assert_eq!(difference.node_count(), example_tree(expected).node_count());
+    }
+
+    #[test]
+    fn test_union_with_const_tree() {
+        let union = EXAMPLE_WORDLIST_2.union(&EXAMPLE_WORDLIST_2, |a, _| a.clone());
+        assert_eq!(
+            union.words().collect::<Vec<_>>(),
+            EXAMPLE_WORDLIST_2.words().collect::<Vec<_>>()
+        );
+        assert_eq!(union.node_count(), EXAMPLE_WORDLIST_2.node_count());
+    }
+
+    #[test]
+    #[should_panic(expected = "trees map strings to chars differently")]
+    fn test_negative_union_different_case_folding() {
+        let full = WordCharTreeBuilder::<String>::new()
+            .with_case_folding(CaseFolding::Full)
+            .build();
+        example_tree(&["arm"]).union(&full, |a, _| a.clone());
+    }
+}
@@ -0,0 +1,78 @@
+use super::WordCharTreeRootNode;
+
+impl<W> WordCharTreeRootNode<'_, W> {
+    /// Create a new tree with the words for which `pred` returns `true`,
+    /// see [`WordTree::filter`](`crate::WordTree::filter`)
+    ///
+    /// The predicate is given the string of the [`char`]s along the path of each word,
+    /// as mapped by the case folding policy and the normalization of the tree,
+    /// along with the word `W`.
+    pub fn filter<'b>(&self, pred: impl Fn(&str, &W) -> bool) -> WordCharTreeRootNode<'b, W>
+    where
+        W: Clone,
+    {
+        WordCharTreeRootNode {
+            tree: self
+                .tree
+                .filter(|chars, word| pred(&chars.iter().collect::<String>(), word)),
+            case_folding: self.case_folding,
+            normalization: self.normalization,
+        }
+    }
+    /// Create a new tree with the same edges, where each word is mapped with `f`,
+    /// see [`WordTree::map`](`crate::WordTree::map`)
+    pub fn map<'b, V>(&self, f: impl Fn(&W) -> V) -> WordCharTreeRootNode<'b, V> {
+        WordCharTreeRootNode {
+            tree: self.tree.map(f),
+            case_folding: self.case_folding,
+            normalization: self.normalization,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::super::{CaseFolding, WordCharTreeBuilder};
+    use test_case::test_case;
+
+    #[test_case(&["arm", "army", "man"], 3, &["arm", "man"] ; "drop longer word")]
+    #[test_case(&["arm", "army", "man"], 2, &[] ; "drop all")]
+    #[test_case(&["a", "ab", "abc", "b"], 1, &["a", "b"] ; "prune subtree")]
+    fn test_filter_by_len(words: &[&str], max_len: usize, expected: &[&str]) {
+        let filtered = example_tree(words).filter(|s, _| s.chars().count() <= max_len);
+        assert_eq!(filtered.words().collect::<Vec<_>>(), expected);
+        assert!(filtered.is_fully_well_formed());
+        assert_eq!(filtered.node_count(), example_tree(expected).node_count());
+        for (idx, word) in expected.iter().enumerate() {
+            assert_eq!(filtered.get_index(word), Some(idx));
+        }
+    }
+
+    #[test]
+    fn test_filter_is_given_folded_string() {
+        let mut builder = WordCharTreeBuilder::new().with_case_folding(CaseFolding::Full);
+        builder.insert("Straße", "Straße");
+        builder.insert("Weg", "Weg");
+        let tree = builder.build();
+        let filtered = tree.filter(|s, _| s.contains("ss"));
+        assert_eq!(filtered.words().collect::<Vec<_>>(), [&"Straße"]);
+        assert_eq!(filtered.case_folding(), CaseFolding::Full);
+    }
+
+    #[test]
+    fn test_map_enum_words_to_strings() {
+        let mapped = EXAMPLE_WORDLIST_2.map(|word| match word {
+            ExampleWords2::Arm => "Arm",
+            ExampleWords2::Army => "Army",
+            ExampleWords2::Man => "Man",
+        });
+        assert_eq!(
+            mapped.words().copied().collect::<Vec<_>>(),
+            ["Arm", "Army", "Man"]
+        );
+        assert_eq!(mapped.get("army"), Some(&"Army"));
+        assert_eq!(mapped.node_count(), EXAMPLE_WORDLIST_2.node_count());
+        assert_eq!(mapped.get_max_depth(), EXAMPLE_WORDLIST_2.get_max_depth());
+    }
+}
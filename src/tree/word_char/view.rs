@@ -0,0 +1,415 @@
+use std::ops::{Range, RangeInclusive};
+
+use super::binary::{
+    case_folding_from_code, crc32, normalization_from_code, CHECKSUM_LEN, EDGE_LEN, HEADER_LEN,
+    NODE_LEN, NO_WORD,
+};
+use super::normalization::edge_chars;
+use super::{CaseFolding, Normalization, ReadTreeError, BINARY_TREE_MAGIC, BINARY_TREE_VERSION};
+
+/// A read-only view of a word char tree in the binary format of
+/// [`WordCharTreeRootNode::write_to`](super::WordCharTreeRootNode::write_to)
+///
+/// The view operates directly on the bytes, so a memory-mapped file or an
+/// [`include_bytes!`] blob can be queried without deserializing it first.
+/// The bytes are validated once, when the view is created. After that, lookups
+/// only read the records that they need.
+///
+/// Words are returned as the bytes from [`BinaryWord::encode_word`](super::BinaryWord::encode_word).
+#[derive(Clone, Copy)]
+pub struct WordCharTreeView<'b> {
+    nodes: &'b [u8],
+    edges: &'b [u8],
+    word_offsets: &'b [u8],
+    word_data: &'b [u8],
+    case_folding: CaseFolding,
+    normalization: Normalization,
+}
+
+pub(super) struct RawNode {
+    pub(super) word: u32,
+    pub(super) first_edge: usize,
+    pub(super) num_edges: usize,
+}
+
+pub(super) struct RawEdge {
+    pub(super) char_lowercase: u32,
+    pub(super) idx_start: u32,
+    pub(super) idx_end: u32,
+    pub(super) child_node: u32,
+}
+
+fn u32_at(bytes: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes(bytes[pos..pos + 4].try_into().unwrap())
+}
+
+/// Split `len` bytes off the front of `bytes`
+fn split_off<'b>(bytes: &mut &'b [u8], len: Option<usize>) -> Result<&'b [u8], ReadTreeError> {
+    match len {
+        Some(len) if len <= bytes.len() => {
+            let (taken, rest) = bytes.split_at(len);
+            *bytes = rest;
+            Ok(taken)
+        }
+        _ => Err(ReadTreeError::Truncated),
+    }
+}
+
+impl<'b> WordCharTreeView<'b> {
+    /// Create a view of the given bytes, after validating them
+    pub fn new(bytes: &'b [u8]) -> Result<Self, ReadTreeError> {
+        if bytes.len() < 6 {
+            return Err(ReadTreeError::Truncated);
+        }
+        if bytes[..4] != BINARY_TREE_MAGIC {
+            return Err(ReadTreeError::BadMagic);
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != BINARY_TREE_VERSION {
+            return Err(ReadTreeError::UnsupportedVersion(version));
+        }
+        if bytes.len() < HEADER_LEN + CHECKSUM_LEN {
+            return Err(ReadTreeError::Truncated);
+        }
+        let (content, stored) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        let stored = u32_at(stored, 0);
+        let computed = crc32(content);
+        if stored != computed {
+            return Err(ReadTreeError::ChecksumMismatch { stored, computed });
+        }
+
+        let case_folding = case_folding_from_code(content[6])
+            .ok_or(ReadTreeError::UnsupportedCaseFolding(content[6]))?;
+        let normalization = normalization_from_code(content[7])
+            .ok_or(ReadTreeError::UnsupportedNormalization(content[7]))?;
+        let num_nodes = u32_at(content, 8) as usize;
+        let num_edges = u32_at(content, 12) as usize;
+        let num_words = u32_at(content, 16) as usize;
+        if num_edges.checked_add(1) != Some(num_nodes) {
+            return Err(ReadTreeError::InvalidHeader);
+        }
+        let mut rest = &content[HEADER_LEN..];
+        let nodes = split_off(&mut rest, num_nodes.checked_mul(NODE_LEN))?;
+        let edges = split_off(&mut rest, num_edges.checked_mul(EDGE_LEN))?;
+        let word_offsets = split_off(
+            &mut rest,
+            num_words.checked_add(1).and_then(|len| len.checked_mul(4)),
+        )?;
+        let data_len = u32_at(word_offsets, num_words * 4) as usize;
+        let word_data = split_off(&mut rest, Some(data_len))?;
+        if !rest.is_empty() {
+            return Err(ReadTreeError::TrailingBytes);
+        }
+
+        let view = Self {
+            nodes,
+            edges,
+            word_offsets,
+            word_data,
+            case_folding,
+            normalization,
+        };
+        view.validate()?;
+        Ok(view)
+    }
+    /// Get the case folding policy that maps the [`char`]s of strings to the edges of the tree
+    pub fn case_folding(&self) -> CaseFolding {
+        self.case_folding
+    }
+    /// Get the Unicode normalization that is applied to strings before case folding
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+    /// Validate the word table and the structure of the tree
+    ///
+    /// Because nodes and edges are in breadth-first order, and the child node of edge `i`
+    /// is node `i + 1`, the tree structure and the `idx_range`s can be validated in a single
+    /// pass over the nodes, without allocating.
+    ///
+    /// Every edge must be one of the edges of a node, and every node other than the root node
+    /// must be the child node of such an edge, so that no records lie outside of the tree.
+    fn validate(&self) -> Result<(), ReadTreeError> {
+        let mut prev_offset = 0;
+        for i in 0..=self.len() {
+            let offset = self.word_offset(i);
+            if offset < prev_offset || (i == 0 && offset != 0) {
+                return Err(ReadTreeError::InvalidWordOffsets);
+            }
+            prev_offset = offset;
+        }
+
+        let num_edges = self.edges.len() / EDGE_LEN;
+        let mut next_first_edge = 0;
+        for node in 0..self.node_count() {
+            // The edge that leads to the node comes before the edges of the node itself.
+            if node > next_first_edge {
+                return Err(ReadTreeError::UnreachableNode { node });
+            }
+            let raw_node = self.node(node);
+            if raw_node.first_edge != next_first_edge
+                || raw_node.num_edges > num_edges - next_first_edge
+            {
+                return Err(ReadTreeError::InvalidEdges { node });
+            }
+            next_first_edge += raw_node.num_edges;
+
+            let words_of_node = if node == 0 {
+                0..self.len()
+            } else {
+                let edge = self.edge(node - 1);
+                edge.idx_start as usize..edge.idx_end as usize + 1
+            };
+            let mut next_idx = words_of_node.start;
+            if raw_node.word != NO_WORD {
+                if node == 0 || raw_node.word as usize != words_of_node.start {
+                    return Err(ReadTreeError::InvalidWordRef {
+                        node,
+                        word: raw_node.word,
+                    });
+                }
+                next_idx += 1;
+            }
+
+            let mut prev_char = None;
+            for edge in raw_node.first_edge..raw_node.first_edge + raw_node.num_edges {
+                let raw_edge = self.edge(edge);
+                let value = raw_edge.char_lowercase;
+                if char::from_u32(value).is_none() {
+                    return Err(ReadTreeError::InvalidChar { edge, value });
+                }
+                if prev_char.is_some_and(|prev_char| prev_char >= value) {
+                    return Err(ReadTreeError::InvalidEdges { node });
+                }
+                prev_char = Some(value);
+                if raw_edge.child_node as usize != edge + 1 || edge < node {
+                    return Err(ReadTreeError::InvalidNodeRef {
+                        edge,
+                        node: raw_edge.child_node,
+                    });
+                }
+                if raw_edge.idx_start as usize != next_idx || raw_edge.idx_end < raw_edge.idx_start
+                {
+                    return Err(ReadTreeError::InconsistentIndexRange { edge });
+                }
+                next_idx = raw_edge.idx_end as usize + 1;
+            }
+            if next_idx != words_of_node.end {
+                return Err(match node {
+                    0 => ReadTreeError::InvalidHeader,
+                    _ => ReadTreeError::InconsistentIndexRange { edge: node - 1 },
+                });
+            }
+        }
+        if next_first_edge != num_edges {
+            return Err(ReadTreeError::UnreachableEdge {
+                edge: next_first_edge,
+            });
+        }
+        Ok(())
+    }
+    /// Get the number of words in the tree
+    pub fn len(&self) -> usize {
+        self.word_offsets.len() / 4 - 1
+    }
+    /// Returns `true` if the tree contains no words
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get the number of nodes in the tree, including the root node
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() / NODE_LEN
+    }
+    /// Returns an iterator over the encoded words of the tree, in the order of their index
+    pub fn words(&self) -> WordCharTreeViewWords<'b> {
+        WordCharTreeViewWords {
+            view: *self,
+            idx_range: 0..self.len(),
+        }
+    }
+    /// Look up the encoded word for the given string
+    ///
+    /// The [`char`]s of the string are mapped by the [case folding](`Self::case_folding`)
+    /// policy and the [normalization](`Self::normalization`) stored in the buffer
+    /// before following the edge for each of them.
+    pub fn get(&self, word: &str) -> Option<&'b [u8]> {
+        self.get_by_index(self.get_index(word)?)
+    }
+    /// Look up the index of the word for the given string
+    pub fn get_index(&self, word: &str) -> Option<usize> {
+        let edge = self.find_edge(word)?;
+        (self.node(edge.child_node as usize).word != NO_WORD).then_some(edge.idx_start as usize)
+    }
+    /// Look up the encoded word at the given index
+    pub fn get_by_index(&self, idx: usize) -> Option<&'b [u8]> {
+        (idx < self.len())
+            .then(|| &self.word_data[self.word_offset(idx)..self.word_offset(idx + 1)])
+    }
+    /// Get the range of indices of the words that start with the given prefix
+    ///
+    /// The empty prefix matches every word in the tree.
+    pub fn prefix_range(&self, prefix: &str) -> Option<RangeInclusive<usize>> {
+        if prefix.is_empty() {
+            return (!self.is_empty()).then(|| 0..=self.len() - 1);
+        }
+        let edge = self.find_edge(prefix)?;
+        Some(edge.idx_start as usize..=edge.idx_end as usize)
+    }
+    /// Returns an iterator over the encoded words that start with the given prefix
+    pub fn words_with_prefix(&self, prefix: &str) -> WordCharTreeViewWords<'b> {
+        let idx_range = match self.prefix_range(prefix) {
+            Some(range) => *range.start()..*range.end() + 1,
+            None => 0..0,
+        };
+        WordCharTreeViewWords {
+            view: *self,
+            idx_range,
+        }
+    }
+    pub(super) fn node(&self, node: usize) -> RawNode {
+        let pos = node * NODE_LEN;
+        RawNode {
+            word: u32_at(self.nodes, pos),
+            first_edge: u32_at(self.nodes, pos + 4) as usize,
+            num_edges: u32_at(self.nodes, pos + 8) as usize,
+        }
+    }
+    pub(super) fn edge(&self, edge: usize) -> RawEdge {
+        let pos = edge * EDGE_LEN;
+        RawEdge {
+            char_lowercase: u32_at(self.edges, pos),
+            idx_start: u32_at(self.edges, pos + 4),
+            idx_end: u32_at(self.edges, pos + 8),
+            child_node: u32_at(self.edges, pos + 12),
+        }
+    }
+    fn word_offset(&self, idx: usize) -> usize {
+        u32_at(self.word_offsets, idx * 4) as usize
+    }
+    /// Find the edge leading to the node for the given string
+    ///
+    /// The edges of each node are sorted by char, so they are binary searched.
+    fn find_edge(&self, s: &str) -> Option<RawEdge> {
+        let mut node = 0;
+        let mut found = None;
+        for c in edge_chars(s, self.case_folding, self.normalization) {
+            let raw_node = self.node(node);
+            let Range { mut start, mut end } =
+                raw_node.first_edge..raw_node.first_edge + raw_node.num_edges;
+            let c = u32::from(c);
+            let edge = loop {
+                if start >= end {
+                    return None;
+                }
+                let mid = start + (end - start) / 2;
+                let edge = self.edge(mid);
+                match edge.char_lowercase.cmp(&c) {
+                    std::cmp::Ordering::Less => start = mid + 1,
+                    std::cmp::Ordering::Greater => end = mid,
+                    std::cmp::Ordering::Equal => break edge,
+                }
+            };
+            node = edge.child_node as usize;
+            found = Some(edge);
+        }
+        found
+    }
+}
+
+/// An iterator over the encoded words of a [`WordCharTreeView`]
+#[derive(Clone)]
+pub struct WordCharTreeViewWords<'b> {
+    view: WordCharTreeView<'b>,
+    idx_range: Range<usize>,
+}
+
+impl<'b> Iterator for WordCharTreeViewWords<'b> {
+    type Item = &'b [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.idx_range.next()?;
+        self.view.get_by_index(idx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idx_range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for WordCharTreeViewWords<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let idx = self.idx_range.next_back()?;
+        self.view.get_by_index(idx)
+    }
+}
+
+impl ExactSizeIterator for WordCharTreeViewWords<'_> {}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::*;
+    use super::super::{BinaryWord, WordCharTreeRootNode};
+    use super::*;
+    use test_case::test_case;
+
+    fn to_bytes<W: BinaryWord>(root: &WordCharTreeRootNode<W>) -> Vec<u8> {
+        let mut bytes = vec![];
+        root.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test_case(EXAMPLE_WORDLIST_EMPTY)]
+    #[test_case(EXAMPLE_WORDLIST_1)]
+    #[test_case(EXAMPLE_WORDLIST_2)]
+    #[test_case(EXAMPLE_WORDLIST_6)]
+    #[test_case(EXAMPLE_WORDLIST_7)]
+    fn test_positive_view_words_same_as_tree<W>(root: WordCharTreeRootNode<W>)
+    where
+        W: BinaryWord + std::fmt::Debug + PartialEq,
+    {
+        let bytes = to_bytes(&root);
+        let view = WordCharTreeView::new(&bytes).unwrap();
+        assert_eq!(view.len(), root.len());
+        assert_eq!(view.node_count(), root.node_count());
+        let words = view
+            .words()
+            .map(|bytes| W::decode_word(bytes).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words.iter().collect::<Vec<_>>(),
+            root.words().collect::<Vec<_>>()
+        );
+        assert_eq!(view.words().rev().count(), view.len());
+    }
+
+    #[test_case("zombie", Some(14))]
+    #[test_case("XRay", Some(10))]
+    #[test_case("man", Some(4))]
+    #[test_case("ma", None)]
+    #[test_case("mangos", None)]
+    #[test_case("", None)]
+    fn test_view_get_index(word: &str, expected_idx: Option<usize>) {
+        let bytes = to_bytes(&EXAMPLE_WORDLIST_7);
+        let view = WordCharTreeView::new(&bytes).unwrap();
+        assert_eq!(view.get_index(word), expected_idx);
+        assert_eq!(
+            view.get(word)
+                .map(|bytes| ExampleWords7::decode_word(bytes).unwrap()),
+            EXAMPLE_WORDLIST_7.get(word).cloned()
+        );
+    }
+
+    #[test_case("", Some(0..=15))]
+    #[test_case("m", Some(3..=8))]
+    #[test_case("man", Some(4..=6))]
+    #[test_case("x", Some(9..=10))]
+    #[test_case("q", None)]
+    fn test_view_prefix_range(prefix: &str, expected_range: Option<RangeInclusive<usize>>) {
+        let bytes = to_bytes(&EXAMPLE_WORDLIST_7);
+        let view = WordCharTreeView::new(&bytes).unwrap();
+        assert_eq!(view.prefix_range(prefix), expected_range);
+        assert_eq!(
+            view.words_with_prefix(prefix).len(),
+            EXAMPLE_WORDLIST_7.words_with_prefix(prefix).count()
+        );
+    }
+}
@@ -1,8 +1,19 @@
 /// An iterator over words `W`
-pub struct Words<'a, W: 'a>(Box<dyn Iterator<Item = &'a W> + 'a>);
+///
+/// The iterator knows the exact number of words that are left,
+/// and can be iterated from both ends, for example with [`Iterator::rev`].
+pub struct Words<'a, W: 'a>(Box<dyn WordsIterator<'a, W> + 'a>);
+
+/// An iterator over words `W` that can be wrapped in [`Words`]
+pub trait WordsIterator<'a, W: 'a>: DoubleEndedIterator<Item = &'a W> + ExactSizeIterator {}
+
+impl<'a, W: 'a, I> WordsIterator<'a, W> for I where
+    I: DoubleEndedIterator<Item = &'a W> + ExactSizeIterator
+{
+}
 
 impl<'a, W> Words<'a, W> {
-    pub fn new(inner_boxed: Box<dyn Iterator<Item = &'a W> + 'a>) -> Self {
+    pub fn new(inner_boxed: Box<dyn WordsIterator<'a, W> + 'a>) -> Self {
         Self(inner_boxed)
     }
 }
@@ -13,4 +24,15 @@ impl<'a, W> Iterator for Words<'a, W> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next()
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
+
+impl<W> DoubleEndedIterator for Words<'_, W> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<W> ExactSizeIterator for Words<'_, W> {}